@@ -0,0 +1,253 @@
+use std::{
+    fmt,
+    hint::black_box,
+    ops::RangeInclusive,
+    process,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+
+/// Growth rate a solver is expected to stay within.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Linear,
+    Linearithmic,
+    Quadratic,
+}
+
+impl Complexity {
+    fn eval(self, n: f64) -> f64 {
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => n,
+            Self::Linearithmic => n * n.ln(),
+            Self::Quadratic => n * n,
+        }
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Constant => "O(1)",
+            Self::Linear => "O(n)",
+            Self::Linearithmic => "O(n log n)",
+            Self::Quadratic => "O(n^2)",
+        })
+    }
+}
+
+/// Settings of the `bench` mode, shared by every day.
+#[derive(Debug, Clone, Args)]
+pub struct Scaling {
+    /// Number of input sizes timed, each twice the previous (1x, 2x, 4x, ...),
+    /// at least two so that there is a growth to fit
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(2..=20))]
+    pub steps: u32,
+
+    /// Timing samples taken per size, the fastest one is kept
+    #[arg(long, default_value_t = 5)]
+    pub samples: u32,
+
+    /// How much the fitted exponent may exceed the declared bound
+    #[arg(long, default_value_t = 0.3)]
+    pub tolerance: f64,
+}
+
+impl Scaling {
+    /// Times `solve` on inputs of `base`, `2 * base`, `4 * base`, ... elements
+    /// built by `generate`, and fits how the run time grows with the size.
+    pub fn measure<I, O>(
+        &self,
+        name: &str,
+        bound: Complexity,
+        base: usize,
+        generate: impl Fn(usize) -> I,
+        solve: impl Fn(&I) -> O,
+    ) -> Report {
+        let sizes: Vec<_> = (0..self.steps).map(|k| base << k).collect();
+
+        // Repeat tiny workloads so that a sample is not dominated by timer noise.
+        let first = generate(sizes[0]);
+        let start = Instant::now();
+        black_box(solve(black_box(&first)));
+        let iters = (Duration::from_millis(5).as_nanos() / start.elapsed().as_nanos().max(1))
+            .clamp(1, 10_000) as u32;
+
+        let times = sizes
+            .iter()
+            .map(|&n| {
+                let input = generate(n);
+                (0..self.samples.max(1))
+                    .map(|_| {
+                        let start = Instant::now();
+                        (0..iters).for_each(|_| {
+                            black_box(solve(black_box(&input)));
+                        });
+                        start.elapsed() / iters
+                    })
+                    .min()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let points = |f: &dyn Fn(f64, f64) -> f64| -> Vec<(f64, f64)> {
+            sizes
+                .iter()
+                .zip(&times)
+                .map(|(&n, t)| {
+                    let n = n as f64;
+                    (n.ln(), f(n, t.as_secs_f64().max(1e-9)).ln())
+                })
+                .collect()
+        };
+
+        Report {
+            name: name.to_string(),
+            bound,
+            exponent: slope(&points(&|_, t| t)),
+            excess: slope(&points(&|n, t| t / bound.eval(n))),
+            tolerance: self.tolerance,
+            sizes,
+            times,
+        }
+    }
+}
+
+/// Least squares slope of `y` over `x`.
+fn slope(points: &[(f64, f64)]) -> f64 {
+    let len = points.len() as f64;
+    let (mx, my) = points
+        .iter()
+        .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x / len, sy + y / len));
+    let (cov, var) = points.iter().fold((0.0, 0.0), |(c, v), (x, y)| {
+        (c + (x - mx) * (y - my), v + (x - mx) * (x - mx))
+    });
+
+    if var == 0.0 {
+        0.0
+    } else {
+        cov / var
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub name: String,
+    pub bound: Complexity,
+    pub sizes: Vec<usize>,
+    pub times: Vec<Duration>,
+    /// Fitted `k` in `time ~ n^k`.
+    pub exponent: f64,
+    /// Growth left over after dividing the times by the declared bound.
+    pub excess: f64,
+    pub tolerance: f64,
+}
+
+impl Report {
+    pub fn exceeds_bound(&self) -> bool {
+        self.excess > self.tolerance
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<10} declared {:<10} fitted n^{:.2}  {}",
+            self.name,
+            self.bound,
+            self.exponent,
+            if self.exceeds_bound() {
+                "GROWS FASTER THAN DECLARED"
+            } else {
+                "ok"
+            }
+        )?;
+        self.sizes
+            .iter()
+            .zip(&self.times)
+            .try_for_each(|(n, t)| writeln!(f, "{:>14} {t:>12.3?}", format!("n = {n}")))
+    }
+}
+
+/// Prints every report and exits with a failure status if any solver grew
+/// faster than its declared bound.
+pub fn check(reports: &[Report]) {
+    reports.iter().for_each(|r| println!("{r}"));
+
+    if reports.iter().any(Report::exceeds_bound) {
+        process::exit(1);
+    }
+}
+
+/// Small xorshift generator, so that benchmark inputs are reproducible
+/// without pulling in a dependency.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (lo, hi) = range.into_inner();
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as usize
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        (1..items.len()).rev().for_each(|i| {
+            let j = self.range(0..=i);
+            items.swap(i, j);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn fits_exponent() {
+        let points: Vec<_> = [1000.0f64, 2000.0, 4000.0, 8000.0]
+            .into_iter()
+            .map(|n| (n.ln(), (3.0 * n * n).ln()))
+            .collect();
+
+        let expected = 2.0;
+        let result = slope(&points);
+
+        assert!((expected - result).abs() < 1e-9);
+    }
+
+    #[test]
+    fn steps_range() {
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            scaling: Scaling,
+        }
+
+        for steps in ["0", "1", "21"] {
+            assert!(Cli::try_parse_from(["bench", "--steps", steps]).is_err());
+        }
+        let cli = Cli::try_parse_from(["bench", "--steps", "2"]).unwrap();
+        assert_eq!(2, cli.scaling.steps);
+    }
+}
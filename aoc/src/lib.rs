@@ -0,0 +1,72 @@
+pub mod bench;
+pub mod repl;
+pub mod snapshot;
+
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use clap::{ArgAction, Args};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer};
+
+/// Flags shared by every day's binary.
+#[derive(Debug, Clone, Args)]
+pub struct Common {
+    /// Read the puzzle input from FILE instead of the bundled one
+    #[arg(short, long, value_name = "FILE", global = true)]
+    pub input: Option<PathBuf>,
+
+    /// Print solver trace events to stderr (-v for debug, -vv for trace)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Write every trace event to FILE as JSON lines
+    #[arg(long, value_name = "FILE", global = true)]
+    pub trace: Option<PathBuf>,
+}
+
+impl Common {
+    /// Returns the contents of `--input`, or `bundled` when it was not given.
+    pub fn read_input(&self, bundled: &'static str) -> io::Result<Cow<'static, str>> {
+        match &self.input {
+            Some(path) => fs::read_to_string(path).map(Cow::Owned),
+            None => Ok(Cow::Borrowed(bundled)),
+        }
+    }
+
+    pub fn level(&self) -> LevelFilter {
+        match self.verbose {
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+
+    /// Installs the global subscriber. The trace file, if any, records
+    /// events of every level regardless of `-v`.
+    pub fn init_tracing(&self) -> io::Result<()> {
+        let stderr = fmt::layer()
+            .with_writer(io::stderr)
+            .with_target(false)
+            .with_filter(self.level());
+
+        let file = self.trace.as_ref().map(File::create).transpose()?.map(|f| {
+            fmt::layer()
+                .json()
+                .with_writer(Mutex::new(f))
+                .with_filter(LevelFilter::TRACE)
+        });
+
+        tracing_subscriber::registry()
+            .with(stderr)
+            .with(file)
+            .init();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+/// Reads commands from stdin until `quit` or EOF, handing each one to `eval`
+/// together with its whitespace separated arguments.
+///
+/// `help` prints `usage`; anything else is up to `eval`, whose output (or
+/// error) is printed before the next prompt.
+pub fn run<S, F>(state: &mut S, usage: &str, mut eval: F) -> io::Result<()>
+where
+    F: FnMut(&mut S, &str, &[&str]) -> Result<String, String>,
+{
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut line = String::new();
+
+    loop {
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let args: Vec<_> = words.collect();
+
+        match command {
+            "quit" | "exit" => return Ok(()),
+            "help" => writeln!(stdout, "{usage}")?,
+            _ => match eval(state, command, &args) {
+                Ok(out) if out.is_empty() => {}
+                Ok(out) => writeln!(stdout, "{out}")?,
+                Err(e) => writeln!(stdout, "error: {e}")?,
+            },
+        }
+    }
+}
+
+/// Parses the `idx`-th argument of a command, naming it in the error.
+pub fn arg<T>(args: &[&str], idx: usize, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let raw = args.get(idx).ok_or_else(|| format!("missing <{name}>"))?;
+    raw.parse()
+        .map_err(|e| format!("invalid <{name}> {raw:?}: {e}"))
+}
+
+/// Like [`arg`], but falls back to `default` when the argument is absent.
+pub fn arg_or<T>(args: &[&str], idx: usize, name: &str, default: T) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if idx < args.len() {
+        arg(args, idx, name)
+    } else {
+        Ok(default)
+    }
+}
@@ -0,0 +1,53 @@
+use std::{env, fs, io::ErrorKind, path::Path};
+
+use similar::TextDiff;
+
+/// Set to `1` to rewrite snapshots with the current output instead of
+/// comparing against them.
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Compares `actual` with the snapshot `dir/name.snap`, panicking with a
+/// line diff on mismatch. Prefer the [`assert_snapshot!`](crate::assert_snapshot)
+/// macro, which points `dir` at the calling crate's `snapshots` directory.
+pub fn assert_snapshot(dir: &Path, name: &str, actual: &str) {
+    let path = dir.join(name).with_extension("snap");
+
+    if env::var(UPDATE_VAR).is_ok_and(|v| v == "1") {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(&path, format!("{actual}\n")).unwrap();
+        return;
+    }
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == ErrorKind::NotFound => panic!(
+            "snapshot {} does not exist, run with {UPDATE_VAR}=1 to create it",
+            path.display()
+        ),
+        Err(e) => panic!("failed to read snapshot {}: {e}", path.display()),
+    };
+    let expected = expected.strip_suffix('\n').unwrap_or(&expected);
+
+    if expected != actual {
+        panic!(
+            "snapshot {} does not match, run with {UPDATE_VAR}=1 if the change is intended\n{}",
+            path.display(),
+            TextDiff::from_lines(expected, actual)
+                .unified_diff()
+                .header("snapshot", "actual")
+        );
+    }
+}
+
+/// Asserts that a rendered value matches `snapshots/<name>.snap` next to the
+/// calling crate's `Cargo.toml`.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::snapshot::assert_snapshot(
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots"),
+            $name,
+            &$actual.to_string(),
+        )
+    };
+}
@@ -0,0 +1,22 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{part1, part2};
+
+fn elves(n: usize) -> Vec<Vec<u32>> {
+    let mut rng = Rng::new(n as u64);
+
+    (0..n)
+        .map(|_| {
+            (0..rng.range(1..=10))
+                .map(|_| rng.range(1000..=60000) as u32)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 100000, elves, |e| part1(e)),
+        scaling.measure("part2", Complexity::Linear, 100000, elves, |e| part2(e)),
+    ]);
+}
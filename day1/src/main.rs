@@ -1,43 +1,170 @@
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
+mod bench;
+mod rebalance;
+mod stats;
+
+use std::{cmp::Reverse, collections::BinaryHeap, mem};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use clap::{Parser, Subcommand};
+use tracing::{info_span, trace};
+
+/// Splits the input into one group of calories per elf. Groups are separated
+/// by blank lines; any other line has to be a calorie count, and at least one
+/// elf has to carry something.
+fn parse_input(input: &str) -> Result<Vec<Vec<u32>>> {
     let mut result = Vec::new();
 
-    let mut inner = Some(Vec::new());
-    for line in input.lines() {
-        if let Ok(n) = line.parse() {
-            inner.get_or_insert_with(Vec::new).push(n);
-        } else {
-            result.push(inner.take().unwrap());
+    let mut inner = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !inner.is_empty() {
+                result.push(mem::take(&mut inner));
+            }
+            continue;
         }
+
+        inner.push(
+            line.parse()
+                .with_context(|| format!("line {}: {line:?} is not a calorie count", i + 1))?,
+        );
+    }
+    if !inner.is_empty() {
+        result.push(inner);
     }
+    ensure!(!result.is_empty(), "no elves carry any items");
 
-    result
+    Ok(result)
 }
 
-fn part1(parsed: &[Vec<u32>]) -> u32 {
-    parsed.iter().map(|inner| inner.iter().sum()).max().unwrap()
+/// Sums an elf's items in 64 bits, which no realistic group can overflow:
+/// it would take more than four billion items of `u32::MAX` calories.
+fn elf_total(inner: &[u32]) -> u64 {
+    inner.iter().map(|&c| u64::from(c)).sum()
 }
 
-fn part2(parsed: &[Vec<u32>]) -> u32 {
-    let mut best3 = [0u32; 3];
+fn part1(parsed: &[Vec<u32>]) -> u64 {
+    parsed.iter().map(|inner| elf_total(inner)).max().unwrap()
+}
 
-    parsed
-        .iter()
-        .map(|inner| inner.iter().sum())
-        .for_each(|el| {
-            if el >= best3[0] {
-                best3[0] = el;
-                best3.sort_unstable();
-            }
-        });
+/// Returns the `n` elves carrying the most calories as `(elf, total)`, best
+/// first. Elves are numbered from 1 in input order and ties go to the elf
+/// that comes first.
+fn top_elves(parsed: &[Vec<u32>], n: usize) -> Vec<(usize, u64)> {
+    // Min-heap on (total, earlier elf), so the root is the elf to drop next.
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+
+    parsed.iter().enumerate().for_each(|(i, inner)| {
+        heap.push(Reverse((elf_total(inner), Reverse(i + 1))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    });
+
+    let mut top: Vec<_> = heap
+        .into_iter()
+        .map(|Reverse((total, Reverse(elf)))| (elf, total))
+        .collect();
+    top.sort_unstable_by_key(|&(elf, total)| (Reverse(total), elf));
+    trace!(?top, "top elves");
+
+    top
+}
+
+fn part2(parsed: &[Vec<u32>]) -> u64 {
+    top_elves(parsed, 3).iter().map(|&(_, total)| total).sum()
+}
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Report statistics about the calorie inventory
+    Stats {
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Redistribute the items so that the heaviest load is as small as possible
+    Rebalance {
+        /// Number of elves to spread the items over [default: as in the input]
+        #[arg(long)]
+        elves: Option<usize>,
 
-    best3.iter().sum()
+        #[arg(long, value_enum, default_value_t = rebalance::Method::Auto)]
+        method: rebalance::Method,
+    },
+    /// List the N elves carrying the most calories
+    Top {
+        #[arg(default_value_t = 3)]
+        n: usize,
+    },
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
 }
 
-fn main() {
-    let parsed = parse_input(include_str!("../input.txt"));
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .context("failed to open trace file")?;
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return Ok(());
+    }
+
+    let parsed = parse_input(
+        &args
+            .common
+            .read_input(include_str!("../input.txt"))
+            .context("failed to read input")?,
+    )?;
+
+    if let Some(Mode::Stats { json }) = args.mode {
+        let stats =
+            stats::Stats::new(&parsed).ok_or_else(|| anyhow!("no elves carry any items"))?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            print!("{stats}");
+        }
+        return Ok(());
+    }
+
+    if let Some(Mode::Rebalance { elves, method }) = args.mode {
+        let elves = elves.unwrap_or(parsed.len());
+        if elves == 0 {
+            return Err(anyhow!("cannot rebalance onto zero elves"));
+        }
+        print!("{}", rebalance::rebalance(&parsed, elves, method));
+        return Ok(());
+    }
 
-    println!("Part 1: {}", part1(&parsed));
-    println!("Part 2: {}", part2(&parsed));
+    if let Some(Mode::Top { n }) = args.mode {
+        for (rank, (elf, total)) in top_elves(&parsed, n).into_iter().enumerate() {
+            println!("{:>3}. elf {elf}: {total}", rank + 1);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(&parsed))
+    );
+    println!(
+        "Part 2: {}",
+        info_span!("part2").in_scope(|| part2(&parsed))
+    );
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -45,7 +172,7 @@ mod tests {
     use super::*;
 
     fn parsed_input() -> Vec<Vec<u32>> {
-        parse_input(include_str!("../test.txt"))
+        parse_input(include_str!("../test.txt")).unwrap()
     }
 
     #[test]
@@ -67,4 +194,64 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn trailing_group() {
+        let input = "1000\n2000\n\n3000\n4000";
+
+        let expected = vec![vec![1000, 2000], vec![3000, 4000]];
+        let result = parse_input(input).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn malformed_line() {
+        let input = "1000\n\n12a\n3000\n";
+
+        let expected = "line 3: \"12a\" is not a calorie count";
+        let result = parse_input(input).unwrap_err().to_string();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn no_elves() {
+        for input in ["", "\n  \n\n"] {
+            let expected = "no elves carry any items";
+            let result = parse_input(input).unwrap_err().to_string();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn top_n() {
+        let input = parsed_input();
+
+        let expected = vec![(4, 24000), (3, 11000), (5, 10000), (1, 6000)];
+        let result = top_elves(&input, 4);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn top_n_ties() {
+        let input = vec![vec![5], vec![7], vec![5], vec![7], vec![5]];
+
+        let expected = vec![(2, 7), (4, 7), (1, 5)];
+        let result = top_elves(&input, 3);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn wide_totals() {
+        let input = vec![vec![u32::MAX, u32::MAX], vec![1], vec![u32::MAX]];
+
+        let expected = 3 * u64::from(u32::MAX) + 1;
+        let result = part2(&input);
+
+        assert_eq!(expected, result);
+    }
 }
@@ -0,0 +1,235 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use clap::ValueEnum;
+
+/// Above this many items `Method::Auto` stops searching for the optimum.
+const EXACT_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Item {
+    pub calories: u32,
+    /// Elf that carried the item in the input, numbered from 1.
+    pub elf: usize,
+}
+
+/// Items split into `k` subsets, each paired with its load.
+type Partition = Vec<(u64, Vec<Item>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Method {
+    /// Exact for small inputs, Karmarkar-Karp otherwise
+    Auto,
+    /// Branch and bound search for the optimal plan
+    Exact,
+    /// Largest differencing heuristic
+    KarmarkarKarp,
+}
+
+/// Items handed to each elf.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub elves: Vec<Vec<Item>>,
+}
+
+impl Plan {
+    pub fn loads(&self) -> Vec<u64> {
+        self.elves
+            .iter()
+            .map(|items| items.iter().map(|i| u64::from(i.calories)).sum())
+            .collect()
+    }
+
+    pub fn max_load(&self) -> u64 {
+        self.loads().into_iter().max().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "max load: {}", self.max_load())?;
+        self.elves
+            .iter()
+            .zip(self.loads())
+            .enumerate()
+            .try_for_each(|(i, (items, load))| {
+                let items = items
+                    .iter()
+                    .map(|item| format!("{} (from elf {})", item.calories, item.elf))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(f, "elf {}: {load} = {items}", i + 1)
+            })
+    }
+}
+
+fn items(parsed: &[Vec<u32>]) -> Vec<Item> {
+    let mut items: Vec<_> = parsed
+        .iter()
+        .enumerate()
+        .flat_map(|(i, inner)| {
+            inner.iter().map(move |&calories| Item {
+                calories,
+                elf: i + 1,
+            })
+        })
+        .collect();
+    items.sort_unstable_by_key(|&item| Reverse(item));
+
+    items
+}
+
+/// Redistributes every item among `elves` elves so that the heaviest load is
+/// as small as `method` manages.
+pub fn rebalance(parsed: &[Vec<u32>], elves: usize, method: Method) -> Plan {
+    assert!(elves > 0, "cannot rebalance onto zero elves");
+    let items = items(parsed);
+
+    match method {
+        Method::Exact => exact(&items, elves),
+        Method::Auto if items.len() <= EXACT_LIMIT => exact(&items, elves),
+        Method::Auto | Method::KarmarkarKarp => karmarkar_karp(&items, elves),
+    }
+}
+
+/// Multi-way largest differencing. Every item starts as its own partial
+/// partition; the two partitions with the largest spread are repeatedly
+/// merged by pairing the heaviest subset of one with the lightest of the other.
+fn karmarkar_karp(items: &[Item], k: usize) -> Plan {
+    // (spread, tie breaker, subsets sorted by load, heaviest first)
+    let mut heap: BinaryHeap<(u64, Reverse<usize>, Partition)> = items
+        .iter()
+        .enumerate()
+        .map(|(i, &item)| {
+            let mut subsets = vec![(0, Vec::new()); k];
+            subsets[0] = (u64::from(item.calories), vec![item]);
+            (u64::from(item.calories), Reverse(i), subsets)
+        })
+        .collect();
+    let mut next = items.len();
+
+    while heap.len() > 1 {
+        let (_, _, a) = heap.pop().unwrap();
+        let (_, _, b) = heap.pop().unwrap();
+
+        let mut merged: Vec<_> = a
+            .into_iter()
+            .zip(b.into_iter().rev())
+            .map(|((la, mut ia), (lb, ib))| {
+                ia.extend(ib);
+                (la + lb, ia)
+            })
+            .collect();
+        merged.sort_unstable_by_key(|&(load, _)| Reverse(load));
+
+        let spread = merged[0].0 - merged[k - 1].0;
+        heap.push((spread, Reverse(next), merged));
+        next += 1;
+    }
+
+    Plan {
+        elves: heap
+            .pop()
+            .map(|(_, _, subsets)| subsets.into_iter().map(|(_, items)| items).collect())
+            .unwrap_or_else(|| vec![Vec::new(); k]),
+    }
+}
+
+struct Search<'a> {
+    items: &'a [Item],
+    lower_bound: u64,
+    loads: Vec<u64>,
+    assignment: Vec<usize>,
+    best: Vec<usize>,
+    best_max: u64,
+}
+
+impl Search<'_> {
+    fn run(&mut self, i: usize) {
+        if self.best_max == self.lower_bound {
+            return;
+        }
+        if i == self.items.len() {
+            self.best_max = self.loads.iter().copied().max().unwrap();
+            self.best.clone_from(&self.assignment);
+            return;
+        }
+
+        let calories = u64::from(self.items[i].calories);
+        for elf in 0..self.loads.len() {
+            // Putting the item on an elf with the same load as an earlier one
+            // only mirrors a branch that was already explored.
+            if self.loads[elf] + calories >= self.best_max
+                || self.loads[..elf].contains(&self.loads[elf])
+            {
+                continue;
+            }
+
+            self.loads[elf] += calories;
+            self.assignment[i] = elf;
+            self.run(i + 1);
+            self.loads[elf] -= calories;
+        }
+    }
+}
+
+/// Branch and bound over `items` (heaviest first), seeded with the
+/// Karmarkar-Karp plan as the bound to beat.
+fn exact(items: &[Item], k: usize) -> Plan {
+    let seed = karmarkar_karp(items, k);
+
+    let total: u64 = items.iter().map(|i| u64::from(i.calories)).sum();
+    let heaviest = items.first().map_or(0, |i| u64::from(i.calories));
+    let mut search = Search {
+        items,
+        lower_bound: total.div_ceil(k as u64).max(heaviest),
+        loads: vec![0; k],
+        assignment: vec![0; items.len()],
+        best: Vec::new(),
+        best_max: seed.max_load(),
+    };
+    search.run(0);
+
+    if search.best.is_empty() {
+        return seed;
+    }
+
+    let mut elves = vec![Vec::new(); k];
+    search
+        .best
+        .iter()
+        .zip(items)
+        .for_each(|(&elf, &item)| elves[elf].push(item));
+
+    Plan { elves }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn get_input() -> Vec<Vec<u32>> {
+        parse_input(include_str!("../test.txt")).unwrap()
+    }
+
+    #[test]
+    fn exact_three_elves() {
+        let input = get_input();
+
+        let expected = 19000;
+        let result = rebalance(&input, 3, Method::Exact);
+
+        assert_eq!(expected, result.max_load());
+        assert_eq!(10, result.elves.iter().map(Vec::len).sum::<usize>());
+    }
+
+    #[test]
+    fn karmarkar_karp_keeps_every_item() {
+        let input = get_input();
+
+        let result = rebalance(&input, 5, Method::KarmarkarKarp);
+
+        assert_eq!(55000, result.loads().iter().sum::<u64>());
+        assert!(result.max_load() >= 11000);
+    }
+}
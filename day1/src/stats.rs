@@ -0,0 +1,171 @@
+use std::{cmp::Reverse, fmt};
+
+use serde::Serialize;
+
+use crate::elf_total;
+
+const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+const BUCKETS: u64 = 10;
+const BAR_WIDTH: usize = 40;
+
+#[derive(Debug, Serialize)]
+pub struct ItemCounts {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Percentile {
+    pub percentile: u8,
+    pub calories: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeaviestItem {
+    pub elf: usize,
+    pub calories: u32,
+}
+
+/// Elves whose total lies in `from..=to`.
+#[derive(Debug, Serialize)]
+pub struct Bucket {
+    pub from: u64,
+    pub to: u64,
+    pub elves: usize,
+}
+
+/// Summary of the calories carried by every elf.
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub elves: usize,
+    pub items_per_elf: ItemCounts,
+    pub total: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub percentiles: Vec<Percentile>,
+    pub heaviest_item: HeaviestItem,
+    pub histogram: Vec<Bucket>,
+}
+
+impl Stats {
+    /// Returns `None` when there are no elves, or only elves without items.
+    pub fn new(parsed: &[Vec<u32>]) -> Option<Self> {
+        let elves = parsed.len();
+        let items: Vec<_> = parsed.iter().map(Vec::len).collect();
+        let mut totals: Vec<_> = parsed.iter().map(|inner| elf_total(inner)).collect();
+        totals.sort_unstable();
+
+        let heaviest_item = parsed
+            .iter()
+            .enumerate()
+            .flat_map(|(i, inner)| inner.iter().map(move |&calories| (i + 1, calories)))
+            // Ties go to the elf that comes first.
+            .max_by_key(|&(elf, calories)| (calories, Reverse(elf)))
+            .map(|(elf, calories)| HeaviestItem { elf, calories })?;
+
+        let total: u64 = totals.iter().sum();
+        let median = if elves.is_multiple_of(2) {
+            (totals[elves / 2 - 1] as f64 + totals[elves / 2] as f64) / 2.0
+        } else {
+            totals[elves / 2] as f64
+        };
+
+        Some(Self {
+            elves,
+            items_per_elf: ItemCounts {
+                min: *items.iter().min()?,
+                max: *items.iter().max()?,
+                mean: items.iter().sum::<usize>() as f64 / elves as f64,
+            },
+            total,
+            mean: total as f64 / elves as f64,
+            median,
+            percentiles: PERCENTILES
+                .into_iter()
+                .map(|percentile| Percentile {
+                    percentile,
+                    calories: nearest_rank(&totals, percentile),
+                })
+                .collect(),
+            heaviest_item,
+            histogram: histogram(&totals),
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn nearest_rank(sorted: &[u64], percentile: u8) -> u64 {
+    let rank = (usize::from(percentile) * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn histogram(sorted: &[u64]) -> Vec<Bucket> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = (max - min) / BUCKETS + 1;
+
+    (0..BUCKETS)
+        .map(|b| min + b * width)
+        .take_while(|&from| from <= max)
+        .map(|from| {
+            let to = from + (width - 1);
+            Bucket {
+                from,
+                to,
+                elves: sorted.iter().filter(|t| (from..=to).contains(t)).count(),
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ItemCounts { min, max, mean } = self.items_per_elf;
+
+        writeln!(f, "elves:          {}", self.elves)?;
+        writeln!(f, "items per elf:  {min}..={max}, mean {mean:.2}")?;
+        writeln!(f, "total calories: {}", self.total)?;
+        writeln!(f, "mean:           {:.2}", self.mean)?;
+        writeln!(f, "median:         {:.1}", self.median)?;
+        for Percentile {
+            percentile,
+            calories,
+        } in &self.percentiles
+        {
+            writeln!(f, "{:<16}{calories}", format!("p{percentile}"))?;
+        }
+        writeln!(
+            f,
+            "heaviest item:  {} (elf {})",
+            self.heaviest_item.calories, self.heaviest_item.elf
+        )?;
+
+        writeln!(f)?;
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(1);
+        self.histogram.iter().try_for_each(|b| {
+            let bar = "#".repeat((b.elves * BAR_WIDTH).div_ceil(most));
+            let line = format!("{:>7}..={:<7} {:>5} {bar}", b.from, b.to, b.elves);
+            writeln!(f, "{}", line.trim_end())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn summary() {
+        let input = parse_input(include_str!("../test.txt")).unwrap();
+
+        let result = Stats::new(&input).unwrap();
+
+        assert_eq!(5, result.elves);
+        assert_eq!(55000, result.total);
+        assert_eq!(10000.0, result.median);
+        assert_eq!(10000, result.heaviest_item.calories);
+        assert_eq!(5, result.heaviest_item.elf);
+        aoc::assert_snapshot!("stats", result);
+    }
+}
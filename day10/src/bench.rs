@@ -0,0 +1,45 @@
+use std::cell::RefCell;
+
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{part1, Cpu, Instruction};
+
+fn program(n: usize) -> Vec<Instruction> {
+    let mut rng = Rng::new(n as u64);
+
+    (0..n)
+        .map(|_| match rng.range(0..=2) {
+            0 => Instruction::Noop,
+            _ => Instruction::Addx(rng.range(0..=10) as i32 - 5),
+        })
+        .collect()
+}
+
+fn run_to_end(program: &[Instruction]) -> i32 {
+    let mut cpu = Cpu::new();
+    cpu.load_program(program);
+    while !cpu.finished() {
+        cpu.run_cycle();
+    }
+
+    cpu.x
+}
+
+/// `part2` draws onto a fixed 240 pixel screen, so it is not benchmarked;
+/// running the whole program covers the same CPU code instead.
+pub fn run(scaling: &Scaling) {
+    // Reused like in `main`, so that only loading the program is measured
+    // rather than growing a fresh instruction queue every time.
+    let cpu = RefCell::new(Cpu::new());
+
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 200000, program, |p| {
+            let mut cpu = cpu.borrow_mut();
+            cpu.reset();
+            part1(&mut cpu, p)
+        }),
+        scaling.measure("run", Complexity::Linear, 200000, program, |p| {
+            run_to_end(p)
+        }),
+    ]);
+}
@@ -1,8 +1,12 @@
+mod bench;
+mod repl;
+
 use std::{
     collections::VecDeque,
     fmt::{self, Write},
 };
 
+use clap::{Parser, Subcommand};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,6 +15,7 @@ use nom::{
     sequence::preceded,
     IResult,
 };
+use tracing::{info_span, trace};
 
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
@@ -33,6 +38,15 @@ impl Instruction {
     }
 }
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Addx(v) => write!(f, "addx {v}"),
+            Self::Noop => f.write_str("noop"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Cpu {
     instructions: VecDeque<Instruction>,
@@ -79,6 +93,7 @@ impl Cpu {
         let (i, c) = self.current.as_mut().unwrap();
         if *c > 1 {
             *c -= 1;
+            trace!(cycle = self.cycle, x = self.x, "cycle");
             return;
         }
 
@@ -90,6 +105,7 @@ impl Cpu {
         }
 
         self.current = self.instructions.pop_front().map(|i| (i, i.cycles()));
+        trace!(cycle = self.cycle, x = self.x, "cycle");
     }
 }
 
@@ -159,12 +175,55 @@ fn part2(cpu: &mut Cpu, instr: &[Instruction]) -> String {
     screen.to_string()
 }
 
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Run the program cycle by cycle interactively
+    Repl,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
 fn main() {
-    let input = parse_input(include_str!("../input.txt"));
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = parse_input(
+        &args
+            .common
+            .read_input(include_str!("../input.txt"))
+            .expect("failed to read input"),
+    );
+
+    if let Some(Mode::Repl) = args.mode {
+        repl::run(input).expect("failed to run repl");
+        return;
+    }
     let mut cpu = Cpu::new();
 
-    println!("Part 1: {}", part1(&mut cpu, &input));
-    println!("Part 2:\n{}", part2(&mut cpu, &input));
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(&mut cpu, &input))
+    );
+    println!(
+        "Part 2:\n{}",
+        info_span!("part2").in_scope(|| part2(&mut cpu, &input))
+    );
 }
 
 #[cfg(test)]
@@ -189,15 +248,8 @@ mod tests {
     fn p2() {
         let (mut c, i) = get_input();
 
-        let expected = r#"##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######....."#
-            .to_string();
         let result = part2(&mut c, &i);
 
-        assert_eq!(expected, result);
+        aoc::assert_snapshot!("p2", result);
     }
 }
@@ -0,0 +1,81 @@
+use std::io;
+
+use aoc::repl::{self, arg};
+
+use super::{Cpu, Instruction};
+
+const USAGE: &str = "\
+cycle N  run the program until cycle N, restarting it if N is in the past
+state    print the current cycle, register and instruction
+reset    restart the program
+quit     leave the repl";
+
+struct Session {
+    cpu: Cpu,
+    program: Vec<Instruction>,
+}
+
+impl Session {
+    fn new(program: Vec<Instruction>) -> Self {
+        let mut cpu = Cpu::new();
+        cpu.load_program(&program);
+
+        Self { cpu, program }
+    }
+
+    fn reset(&mut self) {
+        self.cpu.reset();
+        self.cpu.load_program(&self.program);
+    }
+
+    fn state(&self) -> String {
+        let Cpu { cycle, x, .. } = self.cpu;
+        match self.cpu.current {
+            Some((i, left)) => {
+                format!("cycle {cycle}: x = {x}, executing {i} ({left} cycle(s) left)")
+            }
+            None => format!("cycle {cycle}: x = {x}, program finished"),
+        }
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "cycle" => {
+                let target = arg(args, 0, "N")?;
+                if target < self.cpu.cycle {
+                    self.reset();
+                }
+                while self.cpu.cycle < target && !self.cpu.finished() {
+                    self.cpu.run_cycle();
+                }
+                Ok(self.state())
+            }
+            "state" => Ok(self.state()),
+            "reset" => {
+                self.reset();
+                Ok(self.state())
+            }
+            _ => Err(format!("unknown command {command:?}, try `help`")),
+        }
+    }
+}
+
+pub fn run(program: Vec<Instruction>) -> io::Result<()> {
+    repl::run(&mut Session::new(program), USAGE, Session::eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn cycle() {
+        let mut session = Session::new(parse_input(include_str!("../test.txt")));
+
+        let expected = "cycle 220: x = 18, executing addx 1 (1 cycle(s) left)";
+        let result = session.eval("cycle", &["220"]).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
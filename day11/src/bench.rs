@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use crate::{
+    monkey::{Monkey, Operand, Operation},
+    part1, part2,
+};
+
+/// Eight monkeys holding `n` items between them. Operations only add or
+/// double, so worry levels stay bounded with or without relief.
+fn monkeys(n: usize) -> Vec<Monkey> {
+    let mut rng = Rng::new(n as u64);
+
+    [2, 3, 5, 7, 11, 13, 17, 19]
+        .into_iter()
+        .enumerate()
+        .map(|(i, divisor)| {
+            let mut target = || loop {
+                let t = rng.range(0..=7);
+                if t != i {
+                    break t as u64;
+                }
+            };
+            let (target_if_true, target_if_false) = (target(), target());
+
+            Monkey {
+                items: (0..n / 8)
+                    .map(|_| rng.range(50..=100) as u64)
+                    .collect::<VecDeque<_>>(),
+                operation: match rng.range(0..=3) {
+                    0 => Operation::Mul(Operand::Old, Operand::Const(2)),
+                    _ => Operation::Add(Operand::Old, Operand::Const(rng.range(1..=8) as u64)),
+                },
+                divisor,
+                target_if_true,
+                target_if_false,
+                inspected: 0,
+            }
+        })
+        .collect()
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 4096, monkeys, |m| {
+            part1(m.clone())
+        }),
+        scaling.measure("part2", Complexity::Linear, 64, monkeys, |m| {
+            part2(m.clone())
+        }),
+    ]);
+}
@@ -1,7 +1,12 @@
+mod bench;
 mod monkey;
+mod repl;
 
 use monkey::*;
 
+use clap::{Parser, Subcommand};
+use tracing::info_span;
+
 use std::cmp::Reverse;
 
 fn part1(mut m: Vec<Monkey>) -> usize {
@@ -22,11 +27,51 @@ fn part2(mut m: Vec<Monkey>) -> usize {
     m.into_iter().take(2).map(|m| m.inspected).product()
 }
 
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Play monkey rounds interactively
+    Repl,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
 fn main() {
-    let input = parse_input(include_str!("../input.txt"));
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = parse_input(
+        &args
+            .common
+            .read_input(include_str!("../input.txt"))
+            .expect("failed to read input"),
+    );
+
+    if let Some(Mode::Repl) = args.mode {
+        repl::run(input).expect("failed to run repl");
+        return;
+    }
 
-    println!("Part 1: {}", part1(input.clone()));
-    println!("Part 2: {}", part2(input));
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(input.clone()))
+    );
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(input)));
 }
 
 #[cfg(test)]
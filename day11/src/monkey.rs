@@ -11,6 +11,7 @@ use nom::{
     sequence::{delimited, preceded, terminated, tuple},
     AsChar, Finish, IResult, InputTakeAtPosition, Parser,
 };
+use tracing::{debug, trace};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Operand {
@@ -91,10 +92,16 @@ pub fn round(monkeys: &mut [Monkey], divisor_product: u64) {
                 *target_if_false
             };
 
+            trace!(from = curr, to = target, item, "throw");
             monkeys[target as usize].items.push_back(item);
         }
         mem::swap(&mut buf, &mut monkeys[curr]);
     });
+
+    debug!(
+        inspected = ?monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>(),
+        "round finished"
+    );
 }
 
 fn indented<I, O, E, F>(parser: F) -> impl FnMut(I) -> IResult<I, O, E>
@@ -0,0 +1,100 @@
+use std::io;
+
+use itertools::Itertools;
+
+use aoc::repl::{self, arg, arg_or};
+
+use crate::monkey::{round, Monkey};
+
+const USAGE: &str = "\
+round [N]       play N more rounds (default 1) and print the monkeys
+monkeys         print the items held by each monkey
+relief on|off   divide worry levels by three after inspection (part 1) or not (part 2)
+reset           go back to the starting items
+quit            leave the repl";
+
+struct Session {
+    initial: Vec<Monkey>,
+    monkeys: Vec<Monkey>,
+    rounds: usize,
+    relief: bool,
+}
+
+impl Session {
+    fn new(monkeys: Vec<Monkey>) -> Self {
+        Self {
+            initial: monkeys.clone(),
+            monkeys,
+            rounds: 0,
+            relief: true,
+        }
+    }
+
+    fn monkeys(&self) -> String {
+        let mut out = format!("after round {}:", self.rounds);
+        for (i, m) in self.monkeys.iter().enumerate() {
+            out += &format!(
+                "\nMonkey {i}: {} (inspected {} items)",
+                m.items.iter().join(", "),
+                m.inspected
+            );
+        }
+        out
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "round" => {
+                let n = arg_or(args, 0, "N", 1)?;
+                let divisor_product = if self.relief {
+                    0
+                } else {
+                    self.monkeys.iter().map(|m| m.divisor).product()
+                };
+                (0..n).for_each(|_| round(&mut self.monkeys, divisor_product));
+                self.rounds += n;
+                Ok(self.monkeys())
+            }
+            "monkeys" => Ok(self.monkeys()),
+            "relief" => {
+                self.relief = match arg::<String>(args, 0, "on|off")?.as_str() {
+                    "on" => true,
+                    "off" => false,
+                    other => return Err(format!("expected on or off, got {other:?}")),
+                };
+                Ok(String::new())
+            }
+            "reset" => {
+                self.monkeys = self.initial.clone();
+                self.rounds = 0;
+                Ok(self.monkeys())
+            }
+            _ => Err(format!("unknown command {command:?}, try `help`")),
+        }
+    }
+}
+
+pub fn run(monkeys: Vec<Monkey>) -> io::Result<()> {
+    repl::run(&mut Session::new(monkeys), USAGE, Session::eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monkey::parse_input;
+
+    #[test]
+    fn one_round() {
+        let mut session = Session::new(parse_input(include_str!("../test.txt")));
+
+        let expected = "\
+after round 1:
+Monkey 0: 20, 23, 27, 26 (inspected 2 items)
+Monkey 1: 2080, 25, 167, 207, 401, 1046 (inspected 4 items)
+Monkey 2:  (inspected 3 items)
+Monkey 3:  (inspected 5 items)";
+        let result = session.eval("round", &[]).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
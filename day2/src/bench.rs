@@ -0,0 +1,33 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{fast::Tables, parse_input, part1, part2, Game};
+
+pub fn guide(n: usize) -> String {
+    let mut rng = Rng::new(n as u64);
+
+    (0..n)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+pub fn run(scaling: &Scaling) {
+    let tables = Tables::new(&Game::classic()).unwrap();
+
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 10000, guide, |g| {
+            part1(&parse_input(g, &Game::classic()).unwrap(), &Game::classic())
+        }),
+        scaling.measure("part2", Complexity::Linear, 10000, guide, |g| {
+            part2(&parse_input(g, &Game::classic()).unwrap(), &Game::classic())
+        }),
+        scaling.measure("fast", Complexity::Linear, 100000, guide, |g| {
+            tables.score(g.as_bytes()).unwrap()
+        }),
+    ]);
+}
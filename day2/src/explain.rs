@@ -0,0 +1,224 @@
+use std::fmt;
+
+use crate::{
+    game::{Game, Hand, MatchResult},
+    Round,
+};
+
+/// Our hand, the outcome and the score so far under one part's rules.
+#[derive(Debug, Clone, Copy)]
+pub struct Play {
+    pub ours: Hand,
+    pub result: MatchResult,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    pub opponent: Hand,
+    pub part1: Play,
+    pub part2: Play,
+}
+
+/// Round by round account of the guide, plus what it reveals about the
+/// opponent.
+pub struct Explanation<'a> {
+    game: &'a Game,
+    pub steps: Vec<Step>,
+    /// How often the opponent played each hand.
+    pub frequencies: Vec<u64>,
+    /// `transitions[a][b]` counts the rounds where the opponent played `b`
+    /// right after `a`.
+    pub transitions: Vec<Vec<u64>>,
+    /// Score of the adaptive counter-strategy, see [`adaptive`].
+    pub adaptive: u64,
+}
+
+impl<'a> Explanation<'a> {
+    pub fn new(guide: &[Round], game: &'a Game) -> Self {
+        let n = game.len();
+        let mut frequencies = vec![0; n];
+        let mut transitions = vec![vec![0; n]; n];
+
+        let (mut total1, mut total2) = (0, 0);
+        let steps = guide
+            .iter()
+            .map(|round| {
+                let opponent = round.opponent;
+
+                let ours = Hand(round.response);
+                let result = game.play(ours, opponent);
+                total1 += game.score(ours, result);
+                let part1 = Play {
+                    ours,
+                    result,
+                    total: total1,
+                };
+
+                let ours = game.respond(opponent, round.response);
+                let result = game.play(ours, opponent);
+                total2 += game.score(ours, result);
+                let part2 = Play {
+                    ours,
+                    result,
+                    total: total2,
+                };
+
+                Step {
+                    opponent,
+                    part1,
+                    part2,
+                }
+            })
+            .collect();
+
+        guide
+            .iter()
+            .for_each(|round| frequencies[round.opponent.0] += 1);
+        guide
+            .windows(2)
+            .for_each(|w| transitions[w[0].opponent.0][w[1].opponent.0] += 1);
+
+        Self {
+            game,
+            steps,
+            frequencies,
+            transitions,
+            adaptive: adaptive(guide, game),
+        }
+    }
+}
+
+/// Plays against the opponent using only the rounds seen so far: predict
+/// the hand that most often followed their previous one (or their most
+/// frequent hand while that is unknown, ties going to the earlier hand) and
+/// play whatever scores best against it.
+pub fn adaptive(guide: &[Round], game: &Game) -> u64 {
+    let n = game.len();
+    let mut frequencies = vec![0u64; n];
+    let mut transitions = vec![vec![0u64; n]; n];
+    let mut previous: Option<Hand> = None;
+
+    // Index of the largest count, the first one on ties.
+    let most = |counts: &[u64]| {
+        counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, &c)| c)
+            .filter(|&(_, &c)| c > 0)
+            .map(|(i, _)| Hand(i))
+    };
+
+    guide
+        .iter()
+        .map(|round| {
+            let predicted = previous
+                .and_then(|p| most(&transitions[p.0]))
+                .or_else(|| most(&frequencies))
+                .unwrap_or(Hand(0));
+            let ours = (0..n)
+                .map(Hand)
+                .rev()
+                .max_by_key(|&h| game.score(h, game.play(h, predicted)))
+                .unwrap();
+
+            let opponent = round.opponent;
+            frequencies[opponent.0] += 1;
+            if let Some(p) = previous {
+                transitions[p.0][opponent.0] += 1;
+            }
+            previous = Some(opponent);
+
+            game.score(ours, game.play(ours, opponent))
+        })
+        .sum()
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = self.game;
+        let hands: Vec<_> = (0..game.len()).map(Hand).collect();
+        let w = hands
+            .iter()
+            .map(|&h| game.name(h).len())
+            .max()
+            .unwrap_or(0)
+            .max("opponent".len());
+
+        writeln!(
+            f,
+            "{:>6}  {:<w$}  {:<w$} {:<4} {:>7}  {:<w$} {:<4} {:>7}",
+            "round", "opponent", "part 1", "", "total", "part 2", "", "total"
+        )?;
+        self.steps.iter().enumerate().try_for_each(|(i, s)| {
+            writeln!(
+                f,
+                "{:>6}  {:<w$}  {:<w$} {:<4} {:>7}  {:<w$} {:<4} {:>7}",
+                i + 1,
+                game.name(s.opponent),
+                game.name(s.part1.ours),
+                s.part1.result,
+                s.part1.total,
+                game.name(s.part2.ours),
+                s.part2.result,
+                s.part2.total,
+            )
+        })?;
+
+        writeln!(f, "\nopponent hands:")?;
+        let rounds = self.steps.len().max(1) as f64;
+        hands.iter().try_for_each(|&h| {
+            let count = self.frequencies[h.0];
+            writeln!(
+                f,
+                "  {:<w$} {count:>6} {:>6.1}%",
+                game.name(h),
+                100.0 * count as f64 / rounds
+            )
+        })?;
+
+        writeln!(f, "\ntransitions (row: previous hand, column: next):")?;
+        write!(f, "  {:<w$}", "")?;
+        hands
+            .iter()
+            .try_for_each(|&h| write!(f, " {:>w$}", game.name(h)))?;
+        writeln!(f)?;
+        hands.iter().try_for_each(|&from| {
+            write!(f, "  {:<w$}", game.name(from))?;
+            self.transitions[from.0]
+                .iter()
+                .try_for_each(|count| write!(f, " {count:>w$}"))?;
+            writeln!(f)
+        })?;
+
+        writeln!(f, "\nadaptive counter-strategy: {}", self.adaptive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn breakdown() {
+        let game = Game::classic();
+        let input = parse_input(include_str!("../test.txt"), &game).unwrap();
+
+        let result = Explanation::new(&input, &game);
+
+        let totals: Vec<_> = result
+            .steps
+            .iter()
+            .map(|s| (s.part1.total, s.part2.total))
+            .collect();
+        assert_eq!(vec![(8, 4), (9, 5), (15, 12)], totals);
+        assert_eq!(
+            vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]],
+            result.transitions
+        );
+        assert_eq!(15, result.adaptive);
+        aoc::assert_snapshot!("explain", result);
+    }
+}
@@ -0,0 +1,164 @@
+use rayon::prelude::*;
+
+use crate::game::{Game, Hand};
+
+/// Marks bytes that are not a symbol of the game.
+const NONE: u8 = u8::MAX;
+
+/// Chunks smaller than this are not worth a task of their own.
+const MIN_CHUNK: usize = 1 << 16;
+
+/// Precomputed scores of every (opponent, response) pair, indexed straight
+/// from the two bytes of a round. The classic game has 9 entries per part.
+pub struct Tables {
+    n: usize,
+    opponent: [u8; 256],
+    response: [u8; 256],
+    part1: Vec<u64>,
+    part2: Vec<u64>,
+}
+
+impl Tables {
+    /// Returns `None` when some symbol of `game` is not a single byte, or the
+    /// game has too many hands to index by byte, which only the regular parser
+    /// handles.
+    pub fn new(game: &Game) -> Option<Self> {
+        let n = game.len();
+        if n >= NONE as usize {
+            return None;
+        }
+
+        let lookup = |symbols: Vec<&str>| {
+            let mut table = [NONE; 256];
+            for (i, symbol) in symbols.into_iter().enumerate() {
+                match symbol.as_bytes() {
+                    &[b] if !b.is_ascii_whitespace() => table[b as usize] = i as u8,
+                    _ => return None,
+                }
+            }
+            Some(table)
+        };
+        let opponent = lookup((0..n).map(|i| game.opponent_symbol(Hand(i))).collect())?;
+        let response = lookup((0..n).map(|i| game.response_symbol(i)).collect())?;
+
+        let (part1, part2) = (0..n)
+            .flat_map(|o| (0..n).map(move |r| (Hand(o), r)))
+            .map(|(opponent, r)| {
+                let ours = Hand(r);
+                let p1 = game.score(ours, game.play(ours, opponent));
+                let ours = game.respond(opponent, r);
+                let p2 = game.score(ours, game.play(ours, opponent));
+                (p1, p2)
+            })
+            .unzip();
+
+        Some(Self {
+            n,
+            opponent,
+            response,
+            part1,
+            part2,
+        })
+    }
+
+    /// Scores of both parts for one chunk of whole lines, or `None` if a line
+    /// is not exactly `<opponent> <response>`.
+    fn score_chunk(&self, chunk: &[u8]) -> Option<(u64, u64)> {
+        let (mut p1, mut p2) = (0, 0);
+
+        for line in chunk.split(|&b| b == b'\n') {
+            match *line.strip_suffix(b"\r").unwrap_or(line) {
+                [] => {}
+                [o, b' ', r] => {
+                    let (o, r) = (self.opponent[o as usize], self.response[r as usize]);
+                    if o == NONE || r == NONE {
+                        return None;
+                    }
+                    let i = o as usize * self.n + r as usize;
+                    p1 += self.part1[i];
+                    p2 += self.part2[i];
+                }
+                _ => return None,
+            }
+        }
+
+        Some((p1, p2))
+    }
+
+    /// Scores both parts of a guide at once, splitting it into chunks of
+    /// whole lines that are scored in parallel.
+    ///
+    /// Returns `None` as soon as a line is not in the plain `A X` form (extra
+    /// spaces, unknown symbols, ...), in which case the regular parser should
+    /// take over: it either agrees with the lenient cases or reports the error.
+    pub fn score(&self, input: &[u8]) -> Option<(u64, u64)> {
+        let chunks = rayon::current_num_threads().max(1);
+        let size = (input.len() / chunks).max(MIN_CHUNK);
+
+        line_chunks(input, size)
+            .par_iter()
+            .map(|chunk| self.score_chunk(chunk))
+            .try_reduce(|| (0, 0), |a, b| Some((a.0 + b.0, a.1 + b.1)))
+    }
+}
+
+/// Splits `input` into pieces of roughly `size` bytes, each ending right
+/// after a newline (or at the end of the input).
+fn line_chunks(mut input: &[u8], size: usize) -> Vec<&[u8]> {
+    let mut chunks = Vec::new();
+
+    while input.len() > size {
+        let end = input[size..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(input.len(), |i| size + i + 1);
+        let (chunk, rest) = input.split_at(end);
+        chunks.push(chunk);
+        input = rest;
+    }
+    if !input.is_empty() {
+        chunks.push(input);
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench::guide, parse_input, part1, part2};
+
+    #[test]
+    fn matches_parts() {
+        let game = Game::classic();
+        let tables = Tables::new(&game).unwrap();
+
+        for input in [include_str!("../test.txt").to_string(), guide(100_000)] {
+            let guide = parse_input(&input, &game).unwrap();
+
+            let expected = (part1(&guide, &game), part2(&guide, &game));
+            let result = tables.score(input.as_bytes()).unwrap();
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn chunks_end_on_lines() {
+        let input = b"A X\nB Y\nC Z\nA Y";
+
+        let expected: Vec<&[u8]> = vec![b"A X\nB Y\n", b"C Z\nA Y"];
+        let result = line_chunks(input, 5);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn leaves_odd_lines_to_the_parser() {
+        let tables = Tables::new(&Game::classic()).unwrap();
+
+        assert_eq!(None, tables.score(b"A X\nB  Y\n"));
+        assert_eq!(None, tables.score(b"A X\nB W\n"));
+        assert_eq!(Some((4 + 8, 3 + 4)), tables.score(b"A X\r\n\r\nA Y"));
+    }
+}
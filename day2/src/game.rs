@@ -0,0 +1,254 @@
+use std::fmt;
+
+use anyhow::{bail, ensure, Result};
+use clap::ValueEnum;
+
+use crate::scoring::Scoring;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl fmt::Display for MatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Win => "win",
+            Self::Draw => "draw",
+            Self::Loss => "loss",
+        })
+    }
+}
+
+/// Position of a hand in the game's cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Preset {
+    /// Rock, Paper, Scissors as A/B/C against X/Y/Z
+    Classic,
+    /// Rock, Spock, Paper, Lizard, Scissors as A-E against V-Z
+    Rpsls,
+}
+
+/// A cyclic game with an odd number of hands, where every hand beats the
+/// `(n - 1) / 2` hands right before it and loses to the ones after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    opponent: Vec<String>,
+    response: Vec<String>,
+    scoring: Scoring,
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// Lists the symbols for an error message, as in `X, Y or Z`.
+fn one_of(symbols: &[String]) -> String {
+    match symbols {
+        [] => String::new(),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{} or {last}", init.join(", ")),
+    }
+}
+
+impl Game {
+    /// `names` are in cyclic order, and `opponent` and `response` give the
+    /// symbol of each hand in the two columns of the guide.
+    pub fn new(names: Vec<String>, opponent: Vec<String>, response: Vec<String>) -> Result<Self> {
+        let n = names.len();
+        ensure!(
+            n % 2 == 1,
+            "a cyclic game needs an odd number of hands, got {n}"
+        );
+        for (column, symbols) in [("opponent", &opponent), ("response", &response)] {
+            ensure!(
+                symbols.len() == n,
+                "{n} hands need {n} {column} symbols, got {}",
+                symbols.len()
+            );
+            if let Some((i, s)) = symbols
+                .iter()
+                .enumerate()
+                .find(|&(i, s)| symbols[..i].contains(s))
+            {
+                bail!("{column} symbol {s:?} is used twice (hand {})", i + 1);
+            }
+        }
+
+        Ok(Self {
+            scoring: Scoring::standard(n),
+            names,
+            opponent,
+            response,
+        })
+    }
+
+    pub fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::Classic => Self::classic(),
+            Preset::Rpsls => Self::rpsls(),
+        }
+    }
+
+    pub fn classic() -> Self {
+        Self::new(
+            strings(&["Rock", "Paper", "Scissors"]),
+            strings(&["A", "B", "C"]),
+            strings(&["X", "Y", "Z"]),
+        )
+        .unwrap()
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock, ordered so that each hand beats the
+    /// two before it.
+    pub fn rpsls() -> Self {
+        Self::new(
+            strings(&["Rock", "Spock", "Paper", "Lizard", "Scissors"]),
+            strings(&["A", "B", "C", "D", "E"]),
+            strings(&["V", "W", "X", "Y", "Z"]),
+        )
+        .unwrap()
+    }
+
+    /// Returns the same game with different column symbols, keeping the
+    /// current ones where `None`.
+    pub fn with_symbols(
+        self,
+        opponent: Option<Vec<String>>,
+        response: Option<Vec<String>>,
+    ) -> Result<Self> {
+        Self::new(
+            self.names,
+            opponent.unwrap_or(self.opponent),
+            response.unwrap_or(self.response),
+        )
+    }
+
+    /// Returns the same game scored by `scoring` instead of the standard
+    /// table.
+    pub fn with_scoring(self, scoring: Scoring) -> Result<Self> {
+        ensure!(
+            scoring.hands.len() == self.len(),
+            "{} hands need {} hand scores, got {}",
+            self.len(),
+            self.len(),
+            scoring.hands.len()
+        );
+
+        Ok(Self { scoring, ..self })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, hand: Hand) -> &str {
+        &self.names[hand.0]
+    }
+
+    pub fn opponent(&self, token: &str) -> Result<Hand> {
+        match self.opponent.iter().position(|s| s == token) {
+            Some(i) => Ok(Hand(i)),
+            None => bail!(
+                "unexpected token {token:?}, expected {}",
+                one_of(&self.opponent)
+            ),
+        }
+    }
+
+    pub fn opponent_symbol(&self, hand: Hand) -> &str {
+        &self.opponent[hand.0]
+    }
+
+    pub fn response_symbol(&self, response: usize) -> &str {
+        &self.response[response]
+    }
+
+    /// Index of a response symbol, which part 1 reads as a hand and part 2
+    /// as an offset from the opponent's hand.
+    pub fn response(&self, token: &str) -> Result<usize> {
+        match self.response.iter().position(|s| s == token) {
+            Some(i) => Ok(i),
+            None => bail!(
+                "unexpected token {token:?}, expected {}",
+                one_of(&self.response)
+            ),
+        }
+    }
+
+    /// Result of playing `ours` against `theirs`.
+    pub fn play(&self, ours: Hand, theirs: Hand) -> MatchResult {
+        let n = self.len();
+        match (ours.0 + n - theirs.0) % n {
+            0 => MatchResult::Draw,
+            d if d <= n / 2 => MatchResult::Win,
+            _ => MatchResult::Loss,
+        }
+    }
+
+    /// Points for a round where we played `ours` and got `result`.
+    pub fn score(&self, ours: Hand, result: MatchResult) -> u64 {
+        self.scoring.round(ours, result)
+    }
+
+    /// Hand `steps` places after `hand` in the cycle, or before it when
+    /// `steps` is negative.
+    pub fn shift(&self, hand: Hand, steps: isize) -> Hand {
+        let n = self.len() as isize;
+        Hand((hand.0 as isize + steps).rem_euclid(n) as usize)
+    }
+
+    /// Part 2 reading of a response: the middle symbol asks for a draw, the
+    /// ones after it for ever stronger wins and the ones before it for losses.
+    /// In the classic game that is X to lose, Y to draw and Z to win.
+    pub fn respond(&self, opponent: Hand, response: usize) -> Hand {
+        self.shift(opponent, response as isize - (self.len() / 2) as isize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_hand_beats_the_ones_before_it() {
+        let game = Game::rpsls();
+        let hand = |name| Hand(game.names.iter().position(|n| n == name).unwrap());
+
+        let wins = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(MatchResult::Win, game.play(hand(winner), hand(loser)));
+            assert_eq!(MatchResult::Loss, game.play(hand(loser), hand(winner)));
+        }
+    }
+
+    #[test]
+    fn even_number_of_hands() {
+        let result = Game::new(
+            strings(&["Rock", "Paper"]),
+            strings(&["A", "B"]),
+            strings(&["X", "Y"]),
+        );
+
+        let expected = "a cyclic game needs an odd number of hands, got 2";
+
+        assert_eq!(expected, result.unwrap_err().to_string());
+    }
+}
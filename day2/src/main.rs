@@ -1,114 +1,188 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u16)]
-enum MatchResult {
-    Win = 6,
-    Draw = 3,
-    Loss = 0,
-}
+mod bench;
+mod explain;
+mod fast;
+mod game;
+mod readings;
+mod scoring;
 
-impl TryFrom<&str> for MatchResult {
-    type Error = ();
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use tracing::{info_span, trace};
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Ok(match s {
-            "X" => Self::Loss,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => return Err(()),
-        })
-    }
-}
+use game::{Game, Hand, Preset};
+use scoring::ScoringArgs;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-#[repr(u16)]
-enum Hand {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Round {
+    opponent: Hand,
+    /// Index of the second column's symbol, see [`Game::response`].
+    response: usize,
 }
 
-impl Hand {
-    fn cmp_hands(self, rhs: Self) -> MatchResult {
-        use Hand::{Paper, Rock, Scissors};
-
-        match (self, rhs) {
-            (Rock, Rock) | (Paper, Paper) | (Scissors, Scissors) => MatchResult::Draw,
-            (Paper, Rock) | (Scissors, Paper) | (Rock, Scissors) => MatchResult::Win,
-            _ => MatchResult::Loss,
-        }
-    }
+fn parse_round(line: &str, game: &Game) -> Result<Round> {
+    let mut tokens = line.split_whitespace();
 
-    fn to_get_result(self, match_result: MatchResult) -> Self {
-        use Hand::{Paper, Rock, Scissors};
-        use MatchResult::{Draw, Loss, Win};
-
-        match (self, match_result) {
-            (Rock, Loss) => Scissors,
-            (Rock, Draw) => Rock,
-            (Rock, Win) => Paper,
-            (Paper, Loss) => Rock,
-            (Paper, Draw) => Paper,
-            (Paper, Win) => Scissors,
-            (Scissors, Loss) => Paper,
-            (Scissors, Draw) => Scissors,
-            (Scissors, Win) => Rock,
-        }
+    let opponent = game.opponent(
+        tokens
+            .next()
+            .ok_or_else(|| anyhow!("missing opponent hand"))?,
+    )?;
+    let response = game.response(
+        tokens
+            .next()
+            .ok_or_else(|| anyhow!("missing response after the opponent hand"))?,
+    )?;
+    if let Some(extra) = tokens.next() {
+        bail!("unexpected token {extra:?} after the response");
     }
-}
-
-impl TryFrom<&str> for Hand {
-    type Error = ();
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        match s {
-            "A" | "X" => Ok(Self::Rock),
-            "B" | "Y" => Ok(Self::Paper),
-            "C" | "Z" => Ok(Self::Scissors),
-            _ => Err(()),
-        }
-    }
+    Ok(Round { opponent, response })
 }
 
-fn parse_input(input: &str) -> Vec<(&str, &str)> {
+/// Parses every non-blank line into a round, failing on the first line that
+/// is not an opponent symbol followed by a response symbol of `game`.
+fn parse_input(input: &str, game: &Game) -> Result<Vec<Round>> {
     input
         .lines()
-        .filter_map(|line| line.split_once(' '))
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_round(line, game).with_context(|| format!("line {}", i + 1)))
         .collect()
 }
 
-fn part1(parsed: &[(&str, &str)]) -> u16 {
-    parsed
-        .iter()
-        .filter_map(|&(h1, h2)| Hand::try_from(h1).ok().zip(Hand::try_from(h2).ok()))
-        .fold(0, |acc, (h1, h2)| acc + h2 as u16 + h2.cmp_hands(h1) as u16)
+fn part1(guide: &[Round], game: &Game) -> u64 {
+    guide.iter().fold(0, |acc, round| {
+        let (opponent, ours) = (round.opponent, Hand(round.response));
+        let result = game.play(ours, opponent);
+        trace!(
+            opponent = game.name(opponent),
+            ours = game.name(ours),
+            ?result,
+            "round"
+        );
+        acc + game.score(ours, result)
+    })
 }
 
-fn part2(parsed: &[(&str, &str)]) -> u16 {
-    parsed
-        .iter()
-        .filter_map(|&(hand, result)| {
-            Hand::try_from(hand)
-                .ok()
-                .zip(MatchResult::try_from(result).ok())
-        })
-        .fold(0, |acc, (hand, result)| {
-            acc + hand.to_get_result(result) as u16 + result as u16
-        })
+fn part2(guide: &[Round], game: &Game) -> u64 {
+    guide.iter().fold(0, |acc, round| {
+        let opponent = round.opponent;
+        let ours = game.respond(opponent, round.response);
+        let result = game.play(ours, opponent);
+        trace!(
+            opponent = game.name(opponent),
+            ours = game.name(ours),
+            ?result,
+            "round"
+        );
+        acc + game.score(ours, result)
+    })
 }
 
-fn main() {
-    let input = parse_input(include_str!("../input.txt"));
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    /// Game the strategy guide is written for
+    #[arg(long, value_enum, default_value_t = Preset::Classic, global = true)]
+    game: Preset,
+
+    /// Comma separated opponent symbols, one per hand in cyclic order
+    #[arg(long, value_delimiter = ',', global = true)]
+    opponent_symbols: Option<Vec<String>>,
+
+    /// Comma separated response symbols, one per hand in cyclic order
+    #[arg(long, value_delimiter = ',', global = true)]
+    response_symbols: Option<Vec<String>>,
+
+    #[command(flatten)]
+    scoring: ScoringArgs,
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Walk through the guide round by round and analyse the opponent
+    Explain,
+    /// Score both parts with the table-driven byte-level fast path
+    Fast,
+    /// Score every way of reading the response column and rank them
+    Readings,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .context("failed to open trace file")?;
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return Ok(());
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .context("failed to read input")?;
+    let game = Game::preset(args.game)
+        .with_symbols(args.opponent_symbols, args.response_symbols)
+        .context("invalid game")?;
+    let scoring = args.scoring.resolve(game.len())?;
+    let game = game.with_scoring(scoring).context("invalid scoring")?;
+
+    if let Some(Mode::Fast) = args.mode {
+        let fast = fast::Tables::new(&game)
+            .and_then(|tables| info_span!("fast").in_scope(|| tables.score(input.as_bytes())));
+        // Anything the fast path does not understand goes through the parser,
+        // which either scores it the same way or reports the offending line.
+        let (p1, p2) = match fast {
+            Some(scores) => scores,
+            None => {
+                let guide = parse_input(&input, &game)?;
+                (part1(&guide, &game), part2(&guide, &game))
+            }
+        };
+        println!("Part 1: {p1}");
+        println!("Part 2: {p2}");
+        return Ok(());
+    }
+
+    let guide = parse_input(&input, &game)?;
+
+    if let Some(Mode::Explain) = args.mode {
+        print!("{}", explain::Explanation::new(&guide, &game));
+        return Ok(());
+    }
+
+    if let Some(Mode::Readings) = args.mode {
+        print!("{}", readings::Table(&readings::evaluate(&guide, &game)));
+        return Ok(());
+    }
+
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(&guide, &game))
+    );
+    println!(
+        "Part 2: {}",
+        info_span!("part2").in_scope(|| part2(&guide, &game))
+    );
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn get_input() -> Vec<(&'static str, &'static str)> {
-        parse_input(include_str!("../test.txt"))
+    fn get_input() -> Vec<Round> {
+        parse_input(include_str!("../test.txt"), &Game::classic()).unwrap()
     }
 
     #[test]
@@ -116,7 +190,7 @@ mod tests {
         let input = get_input();
         let expected = 15;
 
-        let result = part1(&input);
+        let result = part1(&input, &Game::classic());
 
         assert_eq!(expected, result);
     }
@@ -126,7 +200,56 @@ mod tests {
         let input = get_input();
         let expected = 12;
 
-        let result = part2(&input);
+        let result = part2(&input, &Game::classic());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn unexpected_token() {
+        let input = "A Y\nB X\nC W\n";
+
+        let expected = "line 3: unexpected token \"W\", expected X, Y or Z";
+        let result = format!("{:#}", parse_input(input, &Game::classic()).unwrap_err());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn rpsls() {
+        let game = Game::rpsls();
+        let input = parse_input("A V\nB Z\nE X\n", &game).unwrap();
+
+        // Rock draws Rock, Scissors lose to Spock, Paper loses to Scissors.
+        assert_eq!(4 + 5 + 3, part1(&input, &game));
+        // Lizard loses to Rock, Lizard beats Spock, Scissors draw Scissors.
+        assert_eq!(4 + 10 + 8, part2(&input, &game));
+    }
+
+    #[test]
+    fn wide_totals() {
+        let input = parse_input(&"A Y\n".repeat(20_000), &Game::classic()).unwrap();
+
+        let expected = 20_000 * 8;
+        let result = part1(&input, &Game::classic());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn custom_scoring() {
+        let input = get_input();
+        let scoring = scoring::Scoring {
+            hands: vec![10, 20, 30],
+            win: 100,
+            draw: 50,
+            loss: 1,
+        };
+        let game = Game::classic().with_scoring(scoring).unwrap();
+
+        // Paper beats Rock, Rock loses to Paper, Scissors draw Scissors.
+        let expected = 120 + 11 + 80;
+        let result = part1(&input, &game);
 
         assert_eq!(expected, result);
     }
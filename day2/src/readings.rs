@@ -0,0 +1,180 @@
+use std::{cmp::Reverse, fmt};
+
+use itertools::Itertools;
+
+use crate::{
+    game::{Game, Hand},
+    Round,
+};
+
+/// How a reading turns the response column into our hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// Every symbol names a hand, as in part 1.
+    Hands,
+    /// Every symbol names an outcome against the opponent, as in part 2.
+    Outcomes,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Hands => "hands",
+            Self::Outcomes => "outcomes",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reading {
+    pub kind: Kind,
+    /// Hand, or outcome index as understood by [`Game::respond`], that each
+    /// response symbol stands for.
+    pub mapping: Vec<usize>,
+    pub score: u64,
+    /// Symbol assignments, as in `X=Rock Y=Paper Z=Scissors`.
+    pub description: String,
+}
+
+impl Reading {
+    /// Whether this is one of the two readings the puzzle itself uses.
+    pub fn is_puzzle(&self) -> bool {
+        self.mapping.iter().enumerate().all(|(i, &m)| i == m)
+    }
+}
+
+/// Name of the outcome that asks for the hand `offset` places away from the
+/// opponent's, see [`Game::respond`].
+fn outcome_name(offset: isize) -> String {
+    match offset {
+        0 => "draw".to_string(),
+        1 => "win".to_string(),
+        -1 => "lose".to_string(),
+        k if k > 0 => format!("win{k}"),
+        k => format!("lose{}", -k),
+    }
+}
+
+/// Scores every permutation of hands and of outcomes over the response
+/// symbols, best first. Ties keep hand readings first, then the order of the
+/// permutations.
+pub fn evaluate(guide: &[Round], game: &Game) -> Vec<Reading> {
+    let n = game.len();
+
+    // Only the number of rounds per (opponent, response) pair matters.
+    let mut counts = vec![vec![0u64; n]; n];
+    guide
+        .iter()
+        .for_each(|round| counts[round.opponent.0][round.response] += 1);
+
+    let score = |ours: &dyn Fn(Hand, usize) -> Hand| -> u64 {
+        (0..n)
+            .cartesian_product(0..n)
+            .filter(|&(o, r)| counts[o][r] > 0)
+            .map(|(o, r)| {
+                let (opponent, ours) = (Hand(o), ours(Hand(o), r));
+                counts[o][r] * game.score(ours, game.play(ours, opponent))
+            })
+            .sum()
+    };
+
+    let mut readings: Vec<_> = [Kind::Hands, Kind::Outcomes]
+        .into_iter()
+        .cartesian_product((0..n).permutations(n))
+        .map(|(kind, mapping)| {
+            let (score, names): (_, Vec<_>) = match kind {
+                Kind::Hands => (
+                    score(&|_, r| Hand(mapping[r])),
+                    mapping
+                        .iter()
+                        .map(|&h| game.name(Hand(h)).to_string())
+                        .collect(),
+                ),
+                Kind::Outcomes => (
+                    score(&|o, r| game.respond(o, mapping[r])),
+                    mapping
+                        .iter()
+                        .map(|&m| outcome_name(m as isize - (n / 2) as isize))
+                        .collect(),
+                ),
+            };
+            let description = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| format!("{}={name}", game.response_symbol(i)))
+                .join(" ");
+
+            Reading {
+                kind,
+                mapping,
+                score,
+                description,
+            }
+        })
+        .collect();
+    readings.sort_by_key(|r| Reverse(r.score));
+
+    readings
+}
+
+/// Ranked table of readings, with the puzzle's own ones marked.
+pub struct Table<'a>(pub &'a [Reading]);
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|r| r.description.len())
+            .max()
+            .unwrap_or(0);
+
+        writeln!(f, "rank  {:<8}  {:<width$}  score", "reading", "mapping")?;
+        self.0.iter().enumerate().try_for_each(|(i, r)| {
+            writeln!(
+                f,
+                "{:>4}  {:<8}  {:<width$}  {:>5}{}",
+                i + 1,
+                r.kind,
+                r.description,
+                r.score,
+                if r.is_puzzle() { "  (puzzle)" } else { "" }
+            )
+        })?;
+
+        if let Some(best) = self.0.first() {
+            writeln!(
+                f,
+                "\nbest: {} ({}) scores {}",
+                best.description, best.kind, best.score
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn every_reading() {
+        let game = Game::classic();
+        let input = parse_input(include_str!("../test.txt"), &game).unwrap();
+
+        let result = evaluate(&input, &game);
+
+        assert_eq!(12, result.len());
+        assert_eq!("X=Scissors Y=Paper Z=Rock", result[0].description);
+        assert_eq!(24, result[0].score);
+
+        let puzzle: Vec<_> = result
+            .iter()
+            .filter(|r| r.is_puzzle())
+            .map(|r| (r.kind, r.score))
+            .collect();
+        assert_eq!(vec![(Kind::Hands, 15), (Kind::Outcomes, 12)], puzzle);
+    }
+}
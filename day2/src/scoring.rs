@@ -0,0 +1,103 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Deserialize;
+
+use crate::game::{Hand, MatchResult};
+
+/// Points awarded for the hand played and for the outcome of a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoring {
+    /// Score of each hand, in the game's cyclic order.
+    pub hands: Vec<u64>,
+    pub win: u64,
+    pub draw: u64,
+    pub loss: u64,
+}
+
+impl Scoring {
+    /// The puzzle's table: hands score their position counted from 1, a win
+    /// 6, a draw 3 and a loss nothing.
+    pub fn standard(hands: usize) -> Self {
+        Self {
+            hands: (1..=hands as u64).collect(),
+            win: 6,
+            draw: 3,
+            loss: 0,
+        }
+    }
+
+    pub fn round(&self, ours: Hand, result: MatchResult) -> u64 {
+        let outcome = match result {
+            MatchResult::Win => self.win,
+            MatchResult::Draw => self.draw,
+            MatchResult::Loss => self.loss,
+        };
+
+        self.hands[ours.0] + outcome
+    }
+}
+
+/// Every field is optional, so that a file only has to mention the scores it
+/// changes.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoringFile {
+    hands: Option<Vec<u64>>,
+    win: Option<u64>,
+    draw: Option<u64>,
+    loss: Option<u64>,
+}
+
+/// Scoring flags. Flags win over `--scoring`, which wins over the standard
+/// table.
+#[derive(Debug, Clone, Args)]
+pub struct ScoringArgs {
+    /// JSON file with any of `hands` (a list), `win`, `draw` and `loss`
+    #[arg(long, value_name = "FILE", global = true)]
+    pub scoring: Option<PathBuf>,
+
+    /// Comma separated score of each hand, in cyclic order
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub hand_scores: Option<Vec<u64>>,
+
+    /// Points for winning a round
+    #[arg(long, global = true)]
+    pub win: Option<u64>,
+
+    /// Points for a draw
+    #[arg(long, global = true)]
+    pub draw: Option<u64>,
+
+    /// Points for losing a round
+    #[arg(long, global = true)]
+    pub loss: Option<u64>,
+}
+
+impl ScoringArgs {
+    /// Builds the table for a game of `hands` hands.
+    pub fn resolve(&self, hands: usize) -> Result<Scoring> {
+        let file = match &self.scoring {
+            Some(path) => {
+                let raw = fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                serde_json::from_str(&raw)
+                    .with_context(|| format!("failed to parse {}", path.display()))?
+            }
+            None => ScoringFile::default(),
+        };
+        let standard = Scoring::standard(hands);
+
+        Ok(Scoring {
+            hands: self
+                .hand_scores
+                .clone()
+                .or(file.hands)
+                .unwrap_or(standard.hands),
+            win: self.win.or(file.win).unwrap_or(standard.win),
+            draw: self.draw.or(file.draw).unwrap_or(standard.draw),
+            loss: self.loss.or(file.loss).unwrap_or(standard.loss),
+        })
+    }
+}
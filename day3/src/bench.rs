@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{char_priority, parse_input, part1, part2};
+
+/// Builds `n` rucksacks in groups of three, each with exactly one item type
+/// shared by its compartments and exactly one badge per group.
+fn rucksacks(n: usize) -> String {
+    let mut rng = Rng::new(n as u64);
+    let mut letters: Vec<_> = ('a'..='z').chain('A'..='Z').collect();
+    let mut out = String::new();
+
+    for _ in 0..n / 3 {
+        rng.shuffle(&mut letters);
+        let (&badge, rest) = letters.split_first().unwrap();
+
+        // Per rucksack: the shared item, then two disjoint pools of fillers.
+        for pools in rest.chunks(17).take(3) {
+            let (&shared, fillers) = pools.split_first().unwrap();
+            let (p1, p2) = fillers.split_at(8);
+            let len = rng.range(9..=20);
+
+            let mut c1 = vec![shared, badge];
+            let mut c2 = vec![shared];
+            c1.extend((2..len).map(|_| *rng.pick(p1)));
+            c2.extend((1..len).map(|_| *rng.pick(p2)));
+            rng.shuffle(&mut c1);
+            rng.shuffle(&mut c2);
+
+            out.extend(c1.into_iter().chain(c2));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// The `HashSet` based solvers the bitset ones replaced, kept to compare
+/// against.
+mod hashset {
+    use super::*;
+
+    pub fn part1(input: &str) -> u64 {
+        let mut hs = HashSet::new();
+
+        input
+            .lines()
+            .map(|line| line.split_at(line.len() / 2))
+            .filter_map(|(c1, c2)| {
+                hs.clear();
+                hs.extend(c1.chars());
+
+                c2.chars().find(|c| hs.contains(c))
+            })
+            .filter_map(char_priority)
+            .map(u64::from)
+            .sum()
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        let mut h1 = HashSet::new();
+        let mut h2 = h1.clone();
+        let mut h3 = h1.clone();
+
+        input
+            .lines()
+            .array_chunks::<3>()
+            .filter_map(|group| {
+                h1.clear();
+                h2.clear();
+                h3.clear();
+
+                h1.extend(group[0].chars());
+                h2.extend(group[1].chars());
+                h3.extend(h1.intersection(&h2));
+
+                group[2].chars().find(|c| h3.contains(c))
+            })
+            .filter_map(char_priority)
+            .map(u64::from)
+            .sum()
+    }
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 3000, rucksacks, |r| {
+            part1(&parse_input(r).unwrap()).unwrap()
+        }),
+        scaling.measure("part2", Complexity::Linear, 3000, rucksacks, |r| {
+            part2(&parse_input(r).unwrap(), 3).unwrap()
+        }),
+        scaling.measure("hs part1", Complexity::Linear, 3000, rucksacks, |r| {
+            hashset::part1(r)
+        }),
+        scaling.measure("hs part2", Complexity::Linear, 3000, rucksacks, |r| {
+            hashset::part2(r)
+        }),
+    ]);
+}
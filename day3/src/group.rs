@@ -0,0 +1,187 @@
+use std::fmt;
+
+use anyhow::{anyhow, ensure, Result};
+use tracing::trace;
+
+use crate::{priority_char, Items, Rucksack};
+
+/// Why a group has no badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// The input ran out before the group was full.
+    Incomplete { rucksacks: usize, size: usize },
+    /// No item type is carried by every rucksack of the group.
+    NoBadge,
+    /// Several item types are carried by every rucksack of the group.
+    Ambiguous(Items),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Incomplete { rucksacks, size } => {
+                write!(f, "incomplete, {rucksacks} of {size} rucksacks")
+            }
+            Self::NoBadge => write!(f, "no item type is common to every rucksack"),
+            Self::Ambiguous(common) => write!(
+                f,
+                "{} candidate badges ({})",
+                common.len(),
+                common.letters()
+            ),
+        }
+    }
+}
+
+/// Consecutive rucksacks carried by one group of elves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Group {
+    /// Position among the groups, counted from 1.
+    pub number: usize,
+    /// Input lines of the group's first and last rucksack.
+    pub lines: (usize, usize),
+    /// Priority of the only item type every rucksack of the group carries,
+    /// or why there is no such item type.
+    pub badge: Result<u16, Problem>,
+}
+
+impl Group {
+    /// The group's badge, or an error naming the group and its problem.
+    pub fn badge(&self) -> Result<u16> {
+        self.badge.map_err(|problem| {
+            anyhow!(
+                "group {} (lines {}-{}): {problem}",
+                self.number,
+                self.lines.0,
+                self.lines.1
+            )
+        })
+    }
+}
+
+/// Splits the rucksacks into groups of `size` and looks for each group's
+/// badge. Every group is returned, including a last one with fewer than
+/// `size` rucksacks.
+pub fn badges(rucksacks: &[Rucksack], size: usize) -> Result<Vec<Group>> {
+    ensure!(size > 0, "group size must be at least 1");
+
+    Ok(rucksacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, group)| {
+            let number = i + 1;
+            let lines = (group[0].line, group[group.len() - 1].line);
+
+            let common = group
+                .iter()
+                .map(Rucksack::items)
+                .fold(Items::ALL, |acc, items| acc & items);
+            let badge = match common.len() {
+                _ if group.len() < size => Err(Problem::Incomplete {
+                    rucksacks: group.len(),
+                    size,
+                }),
+                0 => Err(Problem::NoBadge),
+                1 => Ok(common.priority().unwrap()),
+                _ => Err(Problem::Ambiguous(common)),
+            };
+            if let Ok(badge) = badge {
+                trace!(group = number, badge = %priority_char(badge), "group badge");
+            }
+
+            Group {
+                number,
+                lines,
+                badge,
+            }
+        })
+        .collect())
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "group {:>4}  lines {:>11}  ",
+            self.number,
+            format!("{}-{}", self.lines.0, self.lines.1),
+        )?;
+        match self.badge {
+            Ok(badge) => write!(f, "badge {} ({badge})", priority_char(badge)),
+            Err(problem) => write!(f, "{problem}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn any_group_size() {
+        let input = parse_input("abca\nbdeb\nxyzx\nxwvw\n").unwrap();
+
+        let expected = vec![(1, (1, 2), Ok(2)), (2, (3, 4), Ok(24))];
+        let result: Vec<_> = badges(&input, 2)
+            .unwrap()
+            .iter()
+            .map(|g| (g.number, g.lines, g.badge))
+            .collect();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn incomplete_group() {
+        let input = parse_input("abca\nbdeb\nxyzx\n").unwrap();
+        let groups = badges(&input, 2).unwrap();
+
+        let expected = "group 2 (lines 3-3): incomplete, 1 of 2 rucksacks";
+        let result = groups[1].badge().unwrap_err().to_string();
+
+        assert_eq!(expected, result);
+        assert_eq!(Ok(2), groups[0].badge);
+    }
+
+    #[test]
+    fn every_group_reported() {
+        let input = parse_input(include_str!("../test.txt")).unwrap();
+
+        let expected = vec![
+            "group    1  lines         1-4  no item type is common to every rucksack",
+            "group    2  lines         5-6  incomplete, 2 of 4 rucksacks",
+        ];
+        let result: Vec<_> = badges(&input, 4)
+            .unwrap()
+            .iter()
+            .map(Group::to_string)
+            .collect();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn ambiguous_badges() {
+        let input = parse_input(include_str!("../test.txt")).unwrap();
+        let cases = [
+            (
+                1,
+                "group 1 (lines 1-1): 14 candidate badges (cfghprstvwFJMW)",
+            ),
+            (
+                6,
+                "group 1 (lines 1-6): no item type is common to every rucksack",
+            ),
+        ];
+
+        for (size, expected) in cases {
+            let result = badges(&input, size).unwrap()[0]
+                .badge()
+                .unwrap_err()
+                .to_string();
+
+            assert_eq!(expected, result);
+        }
+    }
+}
@@ -1,72 +1,260 @@
 #![feature(iter_array_chunks)]
 
-use std::collections::HashSet;
+mod bench;
+mod group;
+mod reorg;
+
+use std::ops::{BitAnd, BitOr};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use tracing::{info_span, trace};
 
 // <a, z> -> <1, 26>
 // <A, Z> -> <27, 52>
-fn char_priority(c: char) -> u16 {
+fn char_priority(c: char) -> Option<u16> {
     match c {
         // 'a' as u8 = 97
-        'a'..='z' => c as u8 - 96,
+        'a'..='z' => Some((c as u8 - 96).into()),
         // 'A' as u8 == 65
-        'A'..='Z' => c as u8 - 38,
+        'A'..='Z' => Some((c as u8 - 38).into()),
+        _ => None,
+    }
+}
+
+/// Inverse of [`char_priority`].
+fn priority_char(priority: u16) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        27..=52 => (b'A' + priority as u8 - 27) as char,
         _ => unreachable!(),
     }
-    .into()
 }
 
-fn part1(input: &str) -> u16 {
-    let mut hs = HashSet::new();
+/// Set of item types, with the item of priority `p` stored in bit `p - 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Items(u64);
 
-    input
-        .lines()
-        .map(|line| line.split_at(line.len() / 2))
-        .filter_map(|(c1, c2)| {
-            hs.clear();
-            hs.extend(c1.chars());
+impl Items {
+    /// Every item type from `a` to `Z`.
+    const ALL: Self = Self((1 << 52) - 1);
 
-            c2.chars().find(|c| hs.contains(c))
-        })
-        .map(char_priority)
-        .sum()
+    /// Fails on the first item that is not an ASCII letter.
+    fn new(items: &str) -> Result<Self> {
+        items
+            .chars()
+            .try_fold(Self(0), |set, c| match char_priority(c) {
+                Some(p) => Ok(Self(set.0 | 1 << (p - 1))),
+                None => bail!("item {c:?} is not a letter"),
+            })
+    }
+
+    fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Priority of the item type with the lowest priority, if any.
+    fn priority(self) -> Option<u16> {
+        (self.0 != 0).then(|| self.0.trailing_zeros() as u16 + 1)
+    }
+
+    /// Priorities of every item type in the set, lowest first.
+    fn priorities(self) -> impl Iterator<Item = u16> {
+        (1..=52).filter(move |p| self.0 & 1 << (p - 1) != 0)
+    }
+
+    /// The set as a string of item letters, as in `aB`.
+    fn letters(self) -> String {
+        self.priorities().map(priority_char).collect()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Splits a rucksack into its two compartments, which hold the same number
+/// of items. Items are checked to be letters first, so that splitting by
+/// bytes splits by items.
+fn compartments(line: &str) -> Result<(&str, &str)> {
+    if let Some(c) = line.chars().find(|&c| char_priority(c).is_none()) {
+        bail!("item {c:?} is not a letter");
+    }
+    if !line.len().is_multiple_of(2) {
+        bail!(
+            "odd number of items ({}) cannot fill two equal compartments",
+            line.len()
+        );
+    }
+
+    Ok(line.split_at(line.len() / 2))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rucksack {
+    /// Line of the input, counted from 1.
+    line: usize,
+    first: Items,
+    second: Items,
+}
+
+impl Rucksack {
+    fn items(&self) -> Items {
+        self.first | self.second
+    }
+
+    /// The one item type packed into both compartments.
+    fn misplaced(&self) -> Result<u16> {
+        let shared = self.first & self.second;
+        match shared.len() {
+            0 => bail!("line {}: compartments share no item type", self.line),
+            1 => Ok(shared.priority().unwrap()),
+            n => bail!(
+                "line {}: compartments share {n} item types ({})",
+                self.line,
+                shared.letters()
+            ),
+        }
+    }
 }
 
-fn part2(input: &str) -> u16 {
-    let mut h1 = HashSet::new();
-    let mut h2 = h1.clone();
-    let mut h3 = h1.clone();
+/// Checks that a rucksack holds only letters and splits into two equal
+/// compartments.
+fn parse_rucksack(line: &str) -> Result<(Items, Items)> {
+    let (first, second) = compartments(line)?;
 
+    Ok((Items::new(first)?, Items::new(second)?))
+}
+
+/// Reads every non-blank line as a rucksack, see [`parse_rucksack`].
+fn parse_input(input: &str) -> Result<Vec<Rucksack>> {
     input
         .lines()
-        .array_chunks::<3>()
-        .filter_map(|group| {
-            h1.clear();
-            h2.clear();
-            h3.clear();
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let (first, second) =
+                parse_rucksack(line.trim()).with_context(|| format!("line {}", i + 1))?;
 
-            h1.extend(group[0].chars());
-            h2.extend(group[1].chars());
-            h3.extend(h1.intersection(&h2));
+            Ok(Rucksack {
+                line: i + 1,
+                first,
+                second,
+            })
+        })
+        .collect()
+}
 
-            group[2].chars().find(|c| h3.contains(c))
+fn part1(rucksacks: &[Rucksack]) -> Result<u64> {
+    rucksacks
+        .iter()
+        .map(|rucksack| {
+            let p = rucksack.misplaced()?;
+            trace!(item = %priority_char(p), "misplaced item");
+            Ok(u64::from(p))
         })
-        .map(char_priority)
         .sum()
 }
 
-fn main() {
-    let input = include_str!("../input.txt");
+/// Fails on the first group without a single badge, see [`group::badges`].
+fn part2(rucksacks: &[Rucksack], group_size: usize) -> Result<u64> {
+    group::badges(rucksacks, group_size)?
+        .iter()
+        .map(|g| Ok(u64::from(g.badge()?)))
+        .sum()
+}
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    /// Number of rucksacks carried by each group of elves
+    #[arg(long, default_value_t = 3, global = true)]
+    group_size: usize,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// List every group with its badge, or why it has none
+    Groups,
+    /// Plan the fewest swaps that leave no item type in both compartments
+    Reorganize,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .context("failed to open trace file")?;
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return Ok(());
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .context("failed to read input")?;
+
+    if let Some(Mode::Reorganize) = args.mode {
+        let plans = reorg::plan(&input)?;
+        plans.iter().for_each(|plan| println!("{plan}"));
+        let (moves, impossible) = reorg::totals(&plans);
+        println!("total: {moves} swaps, {impossible} impossible");
+        return Ok(());
+    }
+
+    let rucksacks = parse_input(&input)?;
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+    if let Some(Mode::Groups) = args.mode {
+        let groups = group::badges(&rucksacks, args.group_size)?;
+        groups.iter().for_each(|g| println!("{g}"));
+        let badges: Vec<_> = groups.iter().filter_map(|g| g.badge.ok()).collect();
+        println!(
+            "total: {}, {} groups without a badge",
+            badges.iter().map(|&b| u64::from(b)).sum::<u64>(),
+            groups.len() - badges.len()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(&rucksacks))?
+    );
+    println!(
+        "Part 2: {}",
+        info_span!("part2").in_scope(|| part2(&rucksacks, args.group_size))?
+    );
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn get_input() -> &'static str {
-        include_str!("../test.txt")
+    fn get_input() -> Vec<Rucksack> {
+        parse_input(include_str!("../test.txt")).unwrap()
     }
 
     #[test]
@@ -74,7 +262,7 @@ mod tests {
         let input = get_input();
         let expected = 157;
 
-        let result = part1(input);
+        let result = part1(&input).unwrap();
 
         assert_eq!(expected, result);
     }
@@ -84,8 +272,57 @@ mod tests {
         let input = get_input();
         let expected = 70;
 
-        let result = part2(input);
+        let result = part2(&input, 3).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn priorities_round_trip() {
+        let input: String = ('a'..='z').chain('A'..='Z').collect();
+
+        let expected = Items::ALL;
+        let result = Items::new(&input).unwrap();
 
         assert_eq!(expected, result);
+        assert!(input
+            .chars()
+            .all(|c| priority_char(char_priority(c).unwrap()) == c));
+    }
+
+    #[test]
+    fn invalid_rucksacks() {
+        let cases = [
+            ("abcA\nab1b\n", "line 2: item '1' is not a letter"),
+            ("aéb\n", "line 1: item 'é' is not a letter"),
+            (
+                "abcA\n\nabcab\n",
+                "line 3: odd number of items (5) cannot fill two equal compartments",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let result = format!("{:#}", parse_input(input).unwrap_err());
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn shared_item_types() {
+        let cases = [
+            ("abcd\n", "line 1: compartments share no item type"),
+            (
+                "abcb\nabba\n",
+                "line 2: compartments share 2 item types (ab)",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let rucksacks = parse_input(input).unwrap();
+            let result = part1(&rucksacks).unwrap_err().to_string();
+
+            assert_eq!(expected, result);
+        }
     }
 }
@@ -0,0 +1,177 @@
+use std::{fmt, iter};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{char_priority, compartments, priority_char};
+
+/// Item counts of a compartment, indexed by priority.
+type Counts = [usize; 53];
+
+fn counts(compartment: &str) -> Result<Counts> {
+    compartment.chars().try_fold([0; 53], |mut counts, c| {
+        match char_priority(c) {
+            Some(p) => counts[p as usize] += 1,
+            None => bail!("item {c:?} is not a letter"),
+        }
+        Ok(counts)
+    })
+}
+
+/// One item of the first compartment traded for one of the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swap {
+    pub first: char,
+    pub second: char,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Line of the input, counted from 1.
+    pub line: usize,
+    /// `None` when no arrangement keeps the compartments equal in size.
+    pub swaps: Option<Vec<Swap>>,
+}
+
+/// Fewest swaps that leave every item type in a single compartment of the
+/// rucksack, or `None` if that cannot be done with equal compartments.
+///
+/// Swaps keep the sizes equal, so the question is only which compartment
+/// each type ends up in: the types sent to the first one have to fill it
+/// exactly, and every item already there saves a swap. That is a subset sum
+/// over the item types, maximising the items that stay in place.
+pub fn swaps(rucksack: &str) -> Result<Option<Vec<Swap>>> {
+    let (first, second) = compartments(rucksack)?;
+    let (a, b) = (counts(first)?, counts(second)?);
+    let size = first.len();
+
+    let types: Vec<_> = (1..=52).filter(|&p| a[p] + b[p] > 0).collect();
+
+    // best[i][s]: most items left in place in the first compartment when the
+    // first `i` types put exactly `s` items there.
+    let mut best = vec![vec![None; size + 1]; types.len() + 1];
+    best[0][0] = Some(0);
+    for (i, &p) in types.iter().enumerate() {
+        let total = a[p] + b[p];
+        for s in 0..=size {
+            let skip = best[i][s];
+            let take = (s >= total)
+                .then(|| best[i][s - total].map(|kept| kept + a[p]))
+                .flatten();
+            best[i + 1][s] = skip.max(take);
+        }
+    }
+    if best[types.len()][size].is_none() {
+        return Ok(None);
+    }
+
+    // Walk back to see which types went to the first compartment.
+    let mut to_first = Vec::new();
+    let mut s = size;
+    for (i, &p) in types.iter().enumerate().rev() {
+        let total = a[p] + b[p];
+        if best[i + 1][s] != best[i][s] {
+            to_first.push(p);
+            s -= total;
+        }
+    }
+
+    // Items move out of the compartment their type is not headed for.
+    let moving = |counts: &Counts, to_first_compartment: bool| -> Vec<char> {
+        types
+            .iter()
+            .filter(|p| to_first.contains(p) == to_first_compartment)
+            .flat_map(|&p| iter::repeat_n(priority_char(p as u16), counts[p]))
+            .collect()
+    };
+    let swaps = moving(&a, false)
+        .into_iter()
+        .zip(moving(&b, true))
+        .map(|(first, second)| Swap { first, second })
+        .collect();
+
+    Ok(Some(swaps))
+}
+
+/// Plans every non-blank line of the input, with the same line numbers as
+/// [`parse_input`](crate::parse_input).
+pub fn plan(input: &str) -> Result<Vec<Plan>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Ok(Plan {
+                line: i + 1,
+                swaps: swaps(line.trim()).with_context(|| format!("line {}", i + 1))?,
+            })
+        })
+        .collect()
+}
+
+/// Swaps needed across all plans, and how many rucksacks cannot be fixed.
+pub fn totals(plans: &[Plan]) -> (usize, usize) {
+    plans
+        .iter()
+        .fold((0, 0), |(moves, impossible), plan| match &plan.swaps {
+            Some(swaps) => (moves + swaps.len(), impossible),
+            None => (moves, impossible + 1),
+        })
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {:>4}: ", self.line)?;
+        match &self.swaps {
+            None => write!(f, "impossible"),
+            Some(swaps) if swaps.is_empty() => write!(f, "already sorted"),
+            Some(swaps) => {
+                let list: Vec<_> = swaps
+                    .iter()
+                    .map(|s| format!("{}<->{}", s.first, s.second))
+                    .collect();
+                let plural = if swaps.len() == 1 { "" } else { "s" };
+                write!(f, "{} ({} swap{plural})", list.join(" "), swaps.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_swaps() {
+        let cases = [
+            ("aabb", Some(vec![])),
+            (
+                "abab",
+                Some(vec![Swap {
+                    first: 'b',
+                    second: 'a',
+                }]),
+            ),
+            ("aaaaab", None),
+        ];
+
+        for (input, expected) in cases {
+            let result = swaps(input).unwrap();
+
+            assert_eq!(expected, result);
+        }
+        assert_eq!(
+            "item 'é' is not a letter",
+            swaps("aéb").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_input() {
+        let plans = plan(include_str!("../test.txt")).unwrap();
+
+        let expected = (9, 0);
+        let result = totals(&plans);
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,28 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{parse_input, part1, part2, Assignment};
+
+fn assignments(n: usize) -> Vec<Vec<Assignment>> {
+    let mut rng = Rng::new(n as u64);
+    let mut range = || {
+        let start = rng.range(1..=99);
+        format!("{start}-{}", rng.range(start..=99))
+    };
+
+    let input: String = (0..n)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect();
+
+    parse_input(&input).unwrap()
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 100000, assignments, |a| {
+            part1(a)
+        }),
+        scaling.measure("part2", Complexity::Linear, 100000, assignments, |a| {
+            part2(a)
+        }),
+    ]);
+}
@@ -0,0 +1,173 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::Assignment;
+
+/// Coverage of the sections by every assignment of the input, found with a
+/// single sweep over the range endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    /// Maximal runs of sections covered by at least one elf.
+    pub union: Vec<RangeInclusive<u64>>,
+    /// Runs of sections nobody covers, between the first and last covered one.
+    pub gaps: Vec<RangeInclusive<u64>>,
+    /// Most elves assigned to one section.
+    pub max_elves: usize,
+    /// Runs of sections where `max_elves` elves overlap.
+    pub max_at: Vec<RangeInclusive<u64>>,
+    /// Section assignments beyond the first for each section, i.e. how many
+    /// sections would be freed if every section were cleaned only once.
+    /// Wide enough for several elves sharing nearly every section.
+    pub redundant: u128,
+}
+
+/// Appends `start..=end` to `runs`, merging it with the last run if they
+/// touch.
+fn extend(runs: &mut Vec<RangeInclusive<u64>>, start: u64, end: u64) {
+    match runs.last_mut() {
+        Some(last) if *last.end() + 1 == start => *last = *last.start()..=end,
+        _ => runs.push(start..=end),
+    }
+}
+
+impl Coverage {
+    pub fn new(groups: &[Vec<Assignment>]) -> Self {
+        // +1 where an assignment starts, -1 right after it ends.
+        let mut events: Vec<(u64, isize)> = groups
+            .iter()
+            .flatten()
+            .flat_map(|a| [(a.start, 1), (a.end + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut coverage = Self {
+            union: Vec::new(),
+            gaps: Vec::new(),
+            max_elves: 0,
+            max_at: Vec::new(),
+            redundant: 0,
+        };
+
+        let mut elves = 0;
+        let mut i = 0;
+        while i < events.len() {
+            let at = events[i].0;
+            while i < events.len() && events[i].0 == at {
+                elves += events[i].1;
+                i += 1;
+            }
+            let Some(&(next, _)) = events.get(i) else {
+                break;
+            };
+
+            // Every section in at..next is covered by exactly `elves` elves.
+            let (elves, end) = (elves as usize, next - 1);
+            if elves == 0 {
+                extend(&mut coverage.gaps, at, end);
+                continue;
+            }
+            extend(&mut coverage.union, at, end);
+            coverage.redundant += (elves as u128 - 1) * u128::from(next - at);
+            if elves > coverage.max_elves {
+                coverage.max_elves = elves;
+                coverage.max_at.clear();
+            }
+            if elves == coverage.max_elves {
+                extend(&mut coverage.max_at, at, end);
+            }
+        }
+
+        coverage
+    }
+}
+
+fn runs(runs: &[RangeInclusive<u64>]) -> String {
+    if runs.is_empty() {
+        return "none".to_string();
+    }
+
+    runs.iter()
+        .map(|r| format!("{}-{}", r.start(), r.end()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let covered: u128 = self
+            .union
+            .iter()
+            .map(|r| u128::from(r.end() - r.start()) + 1)
+            .sum();
+
+        writeln!(
+            f,
+            "covered:       {} ({covered} sections)",
+            runs(&self.union)
+        )?;
+        writeln!(f, "gaps:          {}", runs(&self.gaps))?;
+        writeln!(
+            f,
+            "max coverage:  {} elves at {}",
+            self.max_elves,
+            runs(&self.max_at)
+        )?;
+        writeln!(f, "redundant:     {} section assignments", self.redundant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn sweep() {
+        let input = parse_input(include_str!("../test.txt")).unwrap();
+
+        let expected = Coverage {
+            union: vec![2..=9],
+            gaps: vec![],
+            max_elves: 8,
+            max_at: vec![6..=6],
+            redundant: 34,
+        };
+        let result = Coverage::new(&input);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn last_section() {
+        let input = parse_input("0-18446744073709551614,3-4,0-18446744073709551614\n").unwrap();
+
+        let expected = Coverage {
+            union: vec![0..=u64::MAX - 1],
+            gaps: vec![],
+            max_elves: 3,
+            max_at: vec![3..=4],
+            redundant: u128::from(u64::MAX) + 2,
+        };
+        let result = Coverage::new(&input);
+
+        assert_eq!(expected, result);
+        assert!(result
+            .to_string()
+            .contains("(18446744073709551615 sections)"));
+    }
+
+    #[test]
+    fn gaps_between_runs() {
+        let input = parse_input("1-2,4-5\n9-9,3-3\n").unwrap();
+
+        let expected = Coverage {
+            union: vec![1..=5, 9..=9],
+            gaps: vec![6..=8],
+            max_elves: 1,
+            max_at: vec![1..=5, 9..=9],
+            redundant: 0,
+        };
+        let result = Coverage::new(&input);
+
+        assert_eq!(expected, result);
+    }
+}
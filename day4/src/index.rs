@@ -0,0 +1,254 @@
+use std::{cmp::Reverse, fmt, ops::Range, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::Assignment;
+
+/// One elf's assignment, with where it came from in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elf {
+    /// Group the elf belongs to, counted from 1.
+    pub group: usize,
+    /// Position of the elf within its group, counted from 1.
+    pub elf: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "group {} elf {}: {}-{}",
+            self.group, self.elf, self.start, self.end
+        )
+    }
+}
+
+/// Every assignment sorted by start, laid out as an implicit balanced search
+/// tree: the node for `elves[lo..hi]` is its middle element, and `max_end`
+/// holds the largest end below each node so that whole subtrees ending
+/// before a query can be skipped.
+pub struct Index {
+    elves: Vec<Elf>,
+    max_end: Vec<u64>,
+    redundant: Vec<Elf>,
+}
+
+impl Index {
+    pub fn new(groups: &[Vec<Assignment>]) -> Self {
+        let mut elves: Vec<_> = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(i, group)| group.iter().enumerate().map(move |(j, a)| (i, j, a)))
+            .map(|(i, j, a)| Elf {
+                group: i + 1,
+                elf: j + 1,
+                start: a.start,
+                end: a.end,
+            })
+            .collect();
+        // Longer ranges first among equal starts, so that a range is
+        // contained in another exactly when an earlier one ends no sooner.
+        elves.sort_unstable_by_key(|e| (e.start, Reverse(e.end), e.group, e.elf));
+
+        let mut max_end = vec![0; elves.len()];
+        build(&elves, &mut max_end, 0..elves.len());
+
+        let mut furthest = None;
+        let mut redundant: Vec<_> = elves
+            .iter()
+            .filter(|e| {
+                let contained = furthest.is_some_and(|end| end >= e.end);
+                furthest = furthest.max(Some(e.end));
+                contained
+            })
+            .copied()
+            .collect();
+        redundant.sort_unstable();
+
+        Self {
+            elves,
+            max_end,
+            redundant,
+        }
+    }
+
+    /// Elves whose assignment includes `section`.
+    pub fn covering(&self, section: u64) -> Vec<Elf> {
+        self.overlapping(section, section)
+    }
+
+    /// Elves whose assignment shares at least one section with
+    /// `start..=end`. Subtrees that cannot match are skipped, so a query
+    /// visits `O(log n)` nodes per elf it finds.
+    pub fn overlapping(&self, start: u64, end: u64) -> Vec<Elf> {
+        let mut found = Vec::new();
+        self.search(0..self.elves.len(), start, end, &mut found);
+        found.sort_unstable();
+
+        found
+    }
+
+    /// Assignments entirely contained in some other elf's assignment. Of two
+    /// identical assignments, only the later one counts as redundant.
+    pub fn redundant(&self) -> &[Elf] {
+        &self.redundant
+    }
+
+    fn search(&self, range: Range<usize>, start: u64, end: u64, found: &mut Vec<Elf>) {
+        if range.is_empty() {
+            return;
+        }
+        let mid = range.start + range.len() / 2;
+        if self.max_end[mid] < start {
+            return;
+        }
+
+        self.search(range.start..mid, start, end, found);
+        // Everything from here on starts too late.
+        if self.elves[mid].start > end {
+            return;
+        }
+        if self.elves[mid].end >= start {
+            found.push(self.elves[mid]);
+        }
+        self.search(mid + 1..range.end, start, end, found);
+    }
+}
+
+/// Fills `max_end` for the subtree over `range` and returns its maximum.
+fn build(elves: &[Elf], max_end: &mut [u64], range: Range<usize>) -> u64 {
+    if range.is_empty() {
+        return 0;
+    }
+    let mid = range.start + range.len() / 2;
+
+    let left = build(elves, max_end, range.start..mid);
+    let right = build(elves, max_end, mid + 1..range.end);
+    max_end[mid] = elves[mid].end.max(left).max(right);
+
+    max_end[mid]
+}
+
+/// A query of the `query` mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query {
+    /// `S`: who covers section S
+    Covering(u64),
+    /// `A-B`: who overlaps sections A to B
+    Overlapping(u64, u64),
+    /// `redundant`: which assignments are contained in another one
+    Redundant,
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "redundant" {
+            return Ok(Self::Redundant);
+        }
+        let bad = || anyhow!("{s:?} is not a section, a range A-B or \"redundant\"");
+
+        match s.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (
+                    start.parse().map_err(|_| bad())?,
+                    end.parse().map_err(|_| bad())?,
+                );
+                if start > end {
+                    return Err(anyhow!("range {s:?} ends before it starts"));
+                }
+                Ok(Self::Overlapping(start, end))
+            }
+            None => Ok(Self::Covering(s.parse().map_err(|_| bad())?)),
+        }
+    }
+}
+
+impl Query {
+    pub fn run(self, index: &Index) -> Vec<Elf> {
+        match self {
+            Self::Covering(section) => index.covering(section),
+            Self::Overlapping(start, end) => index.overlapping(start, end),
+            Self::Redundant => index.redundant().to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Covering(section) => write!(f, "covering {section}"),
+            Self::Overlapping(start, end) => write!(f, "overlapping {start}-{end}"),
+            Self::Redundant => write!(f, "redundant"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::bench::Rng;
+
+    use super::*;
+    use crate::parse_input;
+
+    fn names(elves: &[Elf]) -> Vec<(usize, usize)> {
+        elves.iter().map(|e| (e.group, e.elf)).collect()
+    }
+
+    #[test]
+    fn queries() {
+        let index = Index::new(&parse_input(include_str!("../test.txt")).unwrap());
+
+        let expected = vec![
+            (1, 2),
+            (3, 1),
+            (4, 1),
+            (4, 2),
+            (5, 1),
+            (5, 2),
+            (6, 1),
+            (6, 2),
+        ];
+        assert_eq!(expected, names(&index.covering(6)));
+
+        let expected = vec![(1, 1), (2, 1), (4, 1), (6, 1)];
+        assert_eq!(expected, names(&index.overlapping(1, 2)));
+
+        // Only 2-8 and 7-9 are not inside another assignment.
+        let kept: Vec<_> = index
+            .elves
+            .iter()
+            .filter(|e| !index.redundant().contains(e))
+            .map(|e| (e.start, e.end))
+            .collect();
+        assert_eq!(vec![(2, 8), (7, 9)], kept);
+        assert_eq!(10, index.redundant().len());
+    }
+
+    #[test]
+    fn matches_linear_scan() {
+        let mut rng = Rng::new(4);
+        let input: String = (0..500)
+            .map(|_| {
+                let (s1, s2) = (rng.range(1..=99), rng.range(1..=99));
+                format!("{s1}-{},{s2}-{}\n", rng.range(s1..=99), rng.range(s2..=99))
+            })
+            .collect();
+        let index = Index::new(&parse_input(&input).unwrap());
+
+        for (start, end) in [(1, 1), (50, 50), (99, 99), (10, 20), (0, 0), (100, 200)] {
+            let mut expected: Vec<_> = index
+                .elves
+                .iter()
+                .filter(|e| e.start <= end && e.end >= start)
+                .copied()
+                .collect();
+            expected.sort_unstable();
+
+            assert_eq!(expected, index.overlapping(start, end));
+        }
+    }
+}
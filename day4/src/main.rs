@@ -1,14 +1,25 @@
 #![feature(result_flattening)]
 #![feature(type_alias_impl_trait)]
 
-use anyhow::{anyhow, Error};
+mod bench;
+mod coverage;
+mod index;
+mod reassign;
 
-use std::str::FromStr;
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
+use clap::{Parser, Subcommand};
+use tracing::{info_span, trace};
 
-#[derive(Debug, Clone)]
+use std::{cmp::Reverse, str::FromStr};
+
+/// Highest section an assignment may cover, leaving room to name the section
+/// right after any range.
+const LAST_SECTION: u64 = u64::MAX - 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Assignment {
-    start: u8,
-    end: u8,
+    start: u64,
+    end: u64,
 }
 
 impl Assignment {
@@ -24,54 +35,216 @@ impl Assignment {
 impl FromStr for Assignment {
     type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let (start, end) = s
             .split_once('-')
-            .ok_or(anyhow!("input does not contain '-'"))?;
+            .ok_or(anyhow!("range {s:?} does not contain '-'"))?;
+        let section = |id: &str| -> Result<u64> {
+            let section = id
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid section {id:?} in range {s:?}"))?;
+            ensure!(
+                section <= LAST_SECTION,
+                "section {section} in range {s:?} is past the last section {LAST_SECTION}"
+            );
+
+            Ok(section)
+        };
+
+        let (start, end) = (section(start)?, section(end)?);
+        if start > end {
+            bail!("range {s:?} ends before it starts");
+        }
+
+        Ok(Self { start, end })
+    }
+}
 
-        Ok(Self {
-            start: start.parse()?,
-            end: end.parse()?,
-        })
+/// Parses a line of comma separated ranges, one per elf of the group. A
+/// group has at least two elves.
+fn parse_group(line: &str) -> Result<Vec<Assignment>> {
+    if !line.contains(',') {
+        bail!("expected two or more ranges separated by ','");
     }
+
+    line.split(',').map(str::parse).collect()
 }
 
-fn parse_input(input: &str) -> Vec<(Assignment, Assignment)> {
+/// Parses every non-blank line into a group of assignments, failing on the
+/// first malformed one.
+fn parse_input(input: &str) -> Result<Vec<Vec<Assignment>>> {
     input
         .lines()
-        .filter_map(|line| line.split_once(','))
-        .filter_map(|(a1, a2)| {
-            Assignment::from_str(a1)
-                .and_then(|v1| Assignment::from_str(a2).map(|v2| (v1, v2)))
-                .ok()
-        })
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_group(line.trim()).with_context(|| format!("line {}", i + 1)))
         .collect()
 }
 
-fn part1(input: &[(Assignment, Assignment)]) -> usize {
+/// The group's assignments by start, with the longest first among equal
+/// starts.
+fn by_start(group: &[Assignment]) -> Vec<&Assignment> {
+    let mut sorted: Vec<_> = group.iter().collect();
+    sorted.sort_unstable_by_key(|a| (a.start, Reverse(a.end)));
+
+    sorted
+}
+
+/// Whether one assignment of the group contains all the others.
+fn contains_all(group: &[Assignment]) -> bool {
+    // Only the assignment that starts first, and is longest among those,
+    // can contain every other one.
+    let sorted = by_start(group);
+    sorted.iter().all(|a| sorted[0].contains(a))
+}
+
+/// Whether any two assignments of the group overlap.
+fn any_overlap(group: &[Assignment]) -> bool {
+    let sorted = by_start(group);
+
+    // Span from the first start to the furthest end seen so far: the next
+    // assignment overlaps one of the earlier ones iff it overlaps the span.
+    let mut span = sorted[0].clone();
+    sorted[1..].iter().any(|a| {
+        let overlaps = span.overlaps(a);
+        span.end = span.end.max(a.end);
+        overlaps
+    })
+}
+
+/// Whether the group leaves no section uncovered between its first start
+/// and its last end.
+fn covers_hull(group: &[Assignment]) -> bool {
+    let sorted = by_start(group);
+
+    let mut end = sorted[0].end;
+    sorted[1..].iter().all(|a| {
+        let touches = a.start.saturating_sub(1) <= end;
+        end = end.max(a.end);
+        touches
+    })
+}
+
+fn part1(input: &[Vec<Assignment>]) -> usize {
+    input
+        .iter()
+        .filter(|group| contains_all(group))
+        .inspect(|group| trace!(?group, "fully contained"))
+        .count()
+}
+
+fn part2(input: &[Vec<Assignment>]) -> usize {
     input
         .iter()
-        .filter(|(a1, a2)| a1.contains(a2) || a2.contains(a1))
+        .filter(|group| any_overlap(group))
+        .inspect(|group| trace!(?group, "overlapping"))
         .count()
 }
 
-fn part2(input: &[(Assignment, Assignment)]) -> usize {
-    input.iter().filter(|(a1, a2)| a1.overlaps(a2)).count()
+/// Groups whose assignments together cover a contiguous run of sections.
+fn covered_hulls(input: &[Vec<Assignment>]) -> usize {
+    input
+        .iter()
+        .filter(|group| covers_hull(group))
+        .inspect(|group| trace!(?group, "hull covered"))
+        .count()
 }
 
-fn main() {
-    let input = parse_input(include_str!("../input.txt"));
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Report how the assignments cover the sections
+    Coverage,
+    /// Look up assignments: S (who covers section S), A-B (who overlaps
+    /// sections A to B) or "redundant" (who is inside another assignment)
+    Query {
+        #[arg(required = true)]
+        queries: Vec<index::Query>,
+    },
+    /// Move elves so that no assignments overlap while keeping every range's
+    /// length, changing as few sections as possible for up to 6 elves and
+    /// keeping larger sets of elves in order
+    Reassign {
+        /// Highest section that may be assigned [default: the highest
+        /// section of the input]
+        #[arg(long)]
+        max_section: Option<u64>,
+        /// Keep every elf of the input apart, not just those of one group
+        #[arg(long)]
+        whole: bool,
+    },
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .context("failed to open trace file")?;
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return Ok(());
+    }
+
+    let input = parse_input(
+        &args
+            .common
+            .read_input(include_str!("../input.txt"))
+            .context("failed to read input")?,
+    )?;
+
+    if let Some(Mode::Query { queries }) = &args.mode {
+        let index = index::Index::new(&input);
+        for query in queries {
+            let elves = query.run(&index);
+            println!("{query}: {} elves", elves.len());
+            elves.iter().for_each(|elf| println!("  {elf}"));
+        }
+        return Ok(());
+    }
+
+    if let Some(Mode::Reassign { max_section, whole }) = args.mode {
+        let highest = input.iter().flatten().map(|a| a.end).max();
+        let bound = max_section.or(highest).unwrap_or(1);
+        let plans = reassign::plans(&input, bound, whole)?;
+        plans.iter().for_each(|plan| print!("{plan}"));
+        let (changed, impossible) = reassign::totals(&plans);
+        println!("total: {changed} sections changed, {impossible} impossible");
+        return Ok(());
+    }
+
+    if let Some(Mode::Coverage) = args.mode {
+        print!("{}", coverage::Coverage::new(&input));
+        return Ok(());
+    }
+
+    println!("Part 1: {}", info_span!("part1").in_scope(|| part1(&input)));
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(&input)));
+    println!(
+        "Covered hulls: {}",
+        info_span!("hulls").in_scope(|| covered_hulls(&input))
+    );
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn get_input() -> Vec<(Assignment, Assignment)> {
-        parse_input(include_str!("../test.txt"))
+    fn get_input() -> Vec<Vec<Assignment>> {
+        parse_input(include_str!("../test.txt")).unwrap()
     }
 
     #[test]
@@ -93,4 +266,51 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn wide_sections() {
+        let input = parse_input("250-300,299-1000000\n").unwrap();
+
+        assert_eq!(0, part1(&input));
+        assert_eq!(1, part2(&input));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let cases = [
+            (
+                "2-4,6-8\n5-3,1-2\n",
+                "line 2: range \"5-3\" ends before it starts",
+            ),
+            (
+                "2-4,6-x\n",
+                "line 1: invalid section \"x\" in range \"6-x\": invalid digit found in string",
+            ),
+            ("2-4,68\n", "line 1: range \"68\" does not contain '-'"),
+            (
+                "2-4,6-8\n\n2-4\n",
+                "line 3: expected two or more ranges separated by ','",
+            ),
+            (
+                "1-18446744073709551615,3-4\n",
+                "line 1: section 18446744073709551615 in range \"1-18446744073709551615\" \
+                 is past the last section 18446744073709551614",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let result = format!("{:#}", parse_input(input).unwrap_err());
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn larger_groups() {
+        let input = parse_input("1-9,2-3,4-8\n1-2,3-4,4-6\n1-2,5-6,3-4\n1-2,6-7,2-3\n").unwrap();
+
+        assert_eq!(1, part1(&input));
+        assert_eq!(3, part2(&input));
+        assert_eq!(3, covered_hulls(&input));
+    }
 }
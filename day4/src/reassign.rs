@@ -0,0 +1,383 @@
+use std::{fmt, iter};
+
+use anyhow::{ensure, Result};
+
+use crate::{any_overlap, index::Elf, Assignment, LAST_SECTION};
+
+/// Groups up to this size are solved over every order of their elves; larger
+/// ones keep the elves in order of their start.
+const EXACT_LIMIT: usize = 6;
+
+impl Assignment {
+    fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Sections of the assignment moved to `start` that it did not cover
+    /// before.
+    fn changed(&self, start: u64) -> u64 {
+        self.start.abs_diff(start).min(self.len())
+    }
+}
+
+/// New assignments for some elves, so that none of them overlap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Group the plan is for, or `None` for every elf of the input at once.
+    pub group: Option<usize>,
+    /// Elves that have to move with their new assignment, or `None` when
+    /// the ranges do not fit side by side within the bound.
+    pub moves: Option<Vec<(Elf, Assignment)>>,
+    /// Whether every order of the elves was tried, so that no plan changes
+    /// fewer sections. Otherwise the elves kept the order of their starts.
+    pub exact: bool,
+}
+
+impl Plan {
+    /// Sections newly assigned across every move of the plan.
+    pub fn changed(&self) -> Option<u64> {
+        let moves = self.moves.as_ref()?;
+
+        Some(
+            moves
+                .iter()
+                .map(|(elf, to)| {
+                    let from = Assignment {
+                        start: elf.start,
+                        end: elf.end,
+                    };
+                    from.changed(to.start)
+                })
+                .sum(),
+        )
+    }
+}
+
+/// Starts worth trying for each elf when `elves` are placed in this order
+/// within sections `1..=bound`.
+///
+/// Elves placed next to each other move together, and the sections they
+/// change only bend where one of them keeps its start or moves by its whole
+/// length. A cheapest placement can therefore slide every run of touching
+/// elves until one of them sits at such a start, or the run meets an edge.
+/// The elves before and after each one still need room, which bounds its
+/// start on both sides.
+fn candidates(elves: &[&Assignment], bound: u64) -> Vec<Vec<u64>> {
+    // before[k]: sections taken by the elves placed before elf `k`.
+    let before: Vec<u64> = iter::once(0)
+        .chain(elves.iter().scan(0, |taken, elf| {
+            *taken += elf.len();
+            Some(*taken)
+        }))
+        .collect();
+    let total = before[elves.len()];
+
+    (0..elves.len())
+        .map(|k| {
+            let (first, last) = (1 + before[k], bound + 1 - (total - before[k]));
+            let mut starts: Vec<_> = elves
+                .iter()
+                .enumerate()
+                .flat_map(|(j, elf)| {
+                    let at = i128::from(elf.start) + i128::from(before[k]) - i128::from(before[j]);
+                    let len = i128::from(elf.len());
+                    [at - len, at, at + len]
+                })
+                .filter_map(|start| u64::try_from(start).ok())
+                .filter(|start| (first..=last).contains(start))
+                .chain([first, last])
+                .collect();
+            starts.sort_unstable();
+            starts.dedup();
+
+            starts
+        })
+        .collect()
+}
+
+/// Cheapest placement of `elves`, in this order from left to right, within
+/// sections `1..=bound`, as the total sections changed and each new start.
+/// The elves must fit, see [`reassign`].
+fn place(elves: &[&Assignment], bound: u64) -> (u64, Vec<u64>) {
+    let candidates = candidates(elves, bound);
+
+    // cost[i]: cheapest placement of the elves so far with the last one
+    // starting at its `i`-th candidate, reached from the previous elf's
+    // candidate `back[k][i]`.
+    let mut cost: Vec<Option<u64>> = candidates[0]
+        .iter()
+        .map(|&s| Some(elves[0].changed(s)))
+        .collect();
+    let mut back = vec![vec![0; candidates[0].len()]];
+
+    for k in 1..elves.len() {
+        let (prev, prev_len) = (&candidates[k - 1], elves[k - 1].len());
+        let mut next = Vec::with_capacity(candidates[k].len());
+        let mut from = Vec::with_capacity(candidates[k].len());
+
+        // Candidates are sorted, so the previous starts that leave room
+        // only ever grow, along with the cheapest of them.
+        let (mut fits, mut cheapest) = (0, None::<usize>);
+        for &start in &candidates[k] {
+            while fits < prev.len() && prev[fits] + prev_len <= start {
+                if cost[fits].is_some() && cheapest.is_none_or(|c| cost[fits] < cost[c]) {
+                    cheapest = Some(fits);
+                }
+                fits += 1;
+            }
+            next.push(
+                cheapest
+                    .and_then(|c| cost[c])
+                    .map(|c| c + elves[k].changed(start)),
+            );
+            from.push(cheapest.unwrap_or_default());
+        }
+
+        cost = next;
+        back.push(from);
+    }
+
+    let (mut i, total) = cost
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| Some((i, (*c)?)))
+        .min_by_key(|&(_, c)| c)
+        .expect("packing the elves from the left always fits");
+
+    // Walk back from the last elf.
+    let mut placed = vec![0; elves.len()];
+    for k in (0..elves.len()).rev() {
+        placed[k] = candidates[k][i];
+        i = back[k][i];
+    }
+
+    (total, placed)
+}
+
+/// Steps `order` to the next permutation in lexicographic order, returning
+/// `false` once it wraps around to the first one.
+fn next_permutation(order: &mut [usize]) -> bool {
+    let Some(i) = order.windows(2).rposition(|w| w[0] < w[1]) else {
+        order.reverse();
+        return false;
+    };
+    let j = order.iter().rposition(|&x| x > order[i]).unwrap();
+    order.swap(i, j);
+    order[i + 1..].reverse();
+
+    true
+}
+
+/// New assignments for `elves` that keep each range's length, overlap
+/// nowhere, stay within sections `1..=bound`, and change as few sections as
+/// possible. Returns `None` when the ranges cannot fit within the bound.
+///
+/// Up to [`EXACT_LIMIT`] elves every left-to-right order is tried; beyond
+/// that the elves keep the order of their starts, and fewer changes may be
+/// possible.
+pub fn reassign(elves: &[Elf], bound: u64) -> Option<Vec<Assignment>> {
+    let assignments: Vec<_> = elves
+        .iter()
+        .map(|e| Assignment {
+            start: e.start,
+            end: e.end,
+        })
+        .collect();
+    let total: u128 = assignments.iter().map(|a| u128::from(a.len())).sum();
+    if elves.is_empty() || total > u128::from(bound) {
+        return None;
+    }
+
+    // Stepping through permutations has to start from the first one to see
+    // them all.
+    let exact = elves.len() <= EXACT_LIMIT;
+    let mut order: Vec<_> = (0..elves.len()).collect();
+    if !exact {
+        order.sort_unstable_by_key(|&i| (elves[i].start, elves[i].end));
+    }
+
+    let mut best: Option<(u64, Vec<u64>, Vec<usize>)> = None;
+    loop {
+        let sorted: Vec<_> = order.iter().map(|&i| &assignments[i]).collect();
+        let (cost, starts) = place(&sorted, bound);
+        if best.as_ref().is_none_or(|(min, ..)| cost < *min) {
+            best = Some((cost, starts, order.clone()));
+        }
+        if !exact || !next_permutation(&mut order) {
+            break;
+        }
+    }
+
+    let (_, starts, order) = best?;
+    let mut result = assignments;
+    for (start, i) in starts.into_iter().zip(order) {
+        let len = result[i].len();
+        result[i] = Assignment {
+            start,
+            end: start + len - 1,
+        };
+    }
+
+    Some(result)
+}
+
+fn elves(group: usize, assignments: &[Assignment]) -> impl Iterator<Item = Elf> + '_ {
+    assignments.iter().enumerate().map(move |(i, a)| Elf {
+        group,
+        elf: i + 1,
+        start: a.start,
+        end: a.end,
+    })
+}
+
+fn plan(group: Option<usize>, elves: Vec<Elf>, bound: u64) -> Plan {
+    let exact = elves.len() <= EXACT_LIMIT;
+    let moves = reassign(&elves, bound).map(|placed| {
+        elves
+            .into_iter()
+            .zip(placed)
+            .filter(|(elf, to)| elf.start != to.start)
+            .collect()
+    });
+
+    Plan {
+        group,
+        moves,
+        exact,
+    }
+}
+
+/// Plans every group with overlapping assignments on its own, or every elf
+/// of the input at once with `whole`, within sections `1..=bound`.
+pub fn plans(input: &[Vec<Assignment>], bound: u64, whole: bool) -> Result<Vec<Plan>> {
+    ensure!(
+        (1..=LAST_SECTION).contains(&bound),
+        "the highest section to assign must be between 1 and {LAST_SECTION}"
+    );
+
+    if whole {
+        let all = input
+            .iter()
+            .enumerate()
+            .flat_map(|(i, group)| elves(i + 1, group))
+            .collect();
+        return Ok(vec![plan(None, all, bound)]);
+    }
+
+    Ok(input
+        .iter()
+        .enumerate()
+        .filter(|(_, group)| any_overlap(group))
+        .map(|(i, group)| plan(Some(i + 1), elves(i + 1, group).collect(), bound))
+        .collect())
+}
+
+/// Sections changed across all plans, and how many of them are impossible.
+pub fn totals(plans: &[Plan]) -> (u64, usize) {
+    plans
+        .iter()
+        .fold((0, 0), |(changed, impossible), plan| match plan.changed() {
+            Some(n) => (changed + n, impossible),
+            None => (changed, impossible + 1),
+        })
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.group {
+            Some(group) => write!(f, "group {group:>4}: ")?,
+            None => write!(f, "all groups: ")?,
+        }
+        let (Some(moves), Some(changed)) = (&self.moves, self.changed()) else {
+            return writeln!(f, "impossible");
+        };
+
+        let plural = if changed == 1 { "" } else { "s" };
+        write!(f, "{changed} section{plural} changed")?;
+        if !self.exact {
+            write!(f, " (elves kept in order, fewer may be possible)")?;
+        }
+        writeln!(f)?;
+        for (elf, to) in moves {
+            writeln!(f, "  {elf} -> {}-{}", to.start, to.end)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, part2};
+
+    #[test]
+    fn fewest_changes() {
+        let cases = [
+            ("3-5,4-6", 99, Some(2)),
+            // Cheapest either way round: the short range moves past the
+            // long one, or the long one past the short one.
+            ("1-10,1-1", 20, Some(1)),
+            ("1-3,2-4,3-5", 9, Some(4)),
+            // The short range jumps to the far side of the long one.
+            ("10-11,8-12", 12, Some(2)),
+            ("1-3,3-5", 5, None),
+        ];
+
+        for (line, bound, expected) in cases {
+            let input = parse_input(line).unwrap();
+
+            let plan = &plans(&input, bound, false).unwrap()[0];
+            let result = plan.changed();
+
+            assert_eq!(expected, result, "{line}");
+            let Some(moves) = &plan.moves else {
+                continue;
+            };
+            let mut placed = input[0].clone();
+            for (elf, to) in moves {
+                placed[elf.elf - 1] = to.clone();
+            }
+            assert!(!any_overlap(&placed), "{line}: {placed:?}");
+            assert!(placed.iter().all(|a| a.start >= 1 && a.end <= bound));
+        }
+    }
+
+    #[test]
+    fn wide_bounds() {
+        let input = parse_input("3-5,4-6\n").unwrap();
+
+        let result = plans(&input, 100_000_000_000, false).unwrap();
+        assert_eq!((2, 0), totals(&result));
+
+        let result = plans(&input, u64::MAX, false).unwrap_err().to_string();
+        assert!(result.starts_with("the highest section to assign must be between 1 and"));
+    }
+
+    #[test]
+    fn larger_groups_keep_order() {
+        let input = parse_input("1-2,2-3,3-4,4-5,5-6,6-7\n1-2,2-3,3-4,4-5,5-6,6-7,7-8\n").unwrap();
+
+        let result = plans(&input, 20, false).unwrap();
+
+        assert_eq!(
+            vec![true, false],
+            result.iter().map(|p| p.exact).collect::<Vec<_>>()
+        );
+        assert!(result[1]
+            .to_string()
+            .contains("(elves kept in order, fewer may be possible)"));
+    }
+
+    #[test]
+    fn test_input() {
+        let input = parse_input(include_str!("../test.txt")).unwrap();
+
+        let result = plans(&input, 99, false).unwrap();
+        assert_eq!(part2(&input), result.len());
+        assert_eq!((10, 0), totals(&result));
+
+        let result = plans(&input, 9, true).unwrap();
+        assert_eq!((0, 1), totals(&result));
+    }
+}
@@ -0,0 +1,55 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{part1, part2, CraneMove, Crates};
+
+/// Nine stacks of crates and `n` moves that never take more crates than a
+/// stack holds.
+fn procedure(n: usize) -> (Crates, Vec<CraneMove>) {
+    let mut rng = Rng::new(n as u64);
+
+    let mut stacks = vec![Vec::new(); 9];
+    stacks.iter_mut().for_each(|stack| {
+        stack.extend((0..50).map(|_| (b'A' + rng.range(0..=25) as u8) as char));
+    });
+    let mut heights: Vec<_> = stacks.iter().map(Vec::len).collect();
+
+    let moves = (0..n)
+        .map(|_| {
+            let from = loop {
+                let i = rng.range(0..=8);
+                if heights[i] > 0 {
+                    break i;
+                }
+            };
+            let to = loop {
+                let i = rng.range(0..=8);
+                if i != from {
+                    break i;
+                }
+            };
+            let count = rng.range(1..=heights[from].min(10));
+
+            heights[from] -= count;
+            heights[to] += count;
+
+            CraneMove {
+                count,
+                from: from + 1,
+                to: to + 1,
+            }
+        })
+        .collect();
+
+    (Crates { stacks }, moves)
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 50000, procedure, |(c, m)| {
+            part1(c.clone(), m)
+        }),
+        scaling.measure("part2", Complexity::Linear, 50000, procedure, |(c, m)| {
+            part2(c.clone(), m)
+        }),
+    ]);
+}
@@ -1,25 +1,109 @@
-#![feature(never_type)]
+mod bench;
+mod repl;
 
-use std::{mem, str::FromStr};
+use std::{error, fmt, mem, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use clap::{Parser, Subcommand};
+use tracing::{debug, info_span, trace};
 
 #[derive(Debug, Clone)]
 struct Crates {
     stacks: Vec<Vec<char>>,
 }
 
+/// Why a crane move cannot be made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveError {
+    /// Stacks are numbered from 1.
+    StackZero,
+    UnknownStack {
+        stack: usize,
+        stacks: usize,
+    },
+    SameStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        holds: usize,
+        count: usize,
+    },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StackZero => write!(f, "stack 0 does not exist, stacks are numbered from 1"),
+            Self::UnknownStack { stack, stacks } => {
+                write!(f, "unknown stack {stack}, there are {stacks} stacks")
+            }
+            Self::SameStack(stack) => write!(f, "stack {stack} is both source and destination"),
+            Self::NotEnoughCrates {
+                stack,
+                holds,
+                count,
+            } => write!(f, "stack {stack} holds {holds} crates, cannot take {count}"),
+        }
+    }
+}
+
+impl error::Error for MoveError {}
+
 impl Crates {
-    fn move_crates_by_one(&mut self, m: &CraneMove) {
+    /// Indices of the source and destination stacks of `m`, once it is
+    /// known the move can be made.
+    fn check(&self, m: &CraneMove) -> Result<(usize, usize), MoveError> {
+        let index = |stack: usize| match stack {
+            0 => Err(MoveError::StackZero),
+            _ if stack > self.stacks.len() => Err(MoveError::UnknownStack {
+                stack,
+                stacks: self.stacks.len(),
+            }),
+            _ => Ok(stack - 1),
+        };
+        let (from, to) = (index(m.from)?, index(m.to)?);
+
+        if from == to {
+            return Err(MoveError::SameStack(m.from));
+        }
+        let holds = self.stacks[from].len();
+        if holds < m.count {
+            return Err(MoveError::NotEnoughCrates {
+                stack: m.from,
+                holds,
+                count: m.count,
+            });
+        }
+
+        Ok((from, to))
+    }
+
+    /// Moves the crates one at a time, or leaves the stacks untouched if the
+    /// move cannot be made.
+    fn move_crates_by_one(&mut self, m: &CraneMove) -> Result<(), MoveError> {
+        let (from, to) = self.check(m)?;
+        debug!(count = m.count, from = m.from, to = m.to, "crane move");
+
         for _ in 0..m.count {
-            let c = self.stacks[m.from - 1].pop().unwrap();
-            self.stacks[m.to - 1].push(c);
+            let c = self.stacks[from].pop().unwrap();
+            self.stacks[to].push(c);
         }
+        trace!(stacks = ?self.stacks);
+
+        Ok(())
     }
 
-    fn move_crates(&mut self, m: &CraneMove) {
-        let mut from = mem::take(&mut self.stacks[m.from - 1]);
+    /// Moves the crates all at once, or leaves the stacks untouched if the
+    /// move cannot be made.
+    fn move_crates(&mut self, m: &CraneMove) -> Result<(), MoveError> {
+        let (from, to) = self.check(m)?;
+        debug!(count = m.count, from = m.from, to = m.to, "crane move");
+
+        let mut taken = mem::take(&mut self.stacks[from]);
+        self.stacks[to].extend(taken.drain((taken.len() - m.count)..));
+        self.stacks[from] = taken;
+        trace!(stacks = ?self.stacks);
 
-        self.stacks[m.to - 1].extend(from.drain((from.len() - m.count)..));
-        self.stacks[m.from - 1] = from;
+        Ok(())
     }
 
     fn top_crates(&self) -> String {
@@ -31,22 +115,74 @@ impl Crates {
     }
 }
 
+impl fmt::Display for Crates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or_default();
+
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{c}]"),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(f, "{}", row.trim_end())?;
+        }
+
+        (1..=self.stacks.len()).try_for_each(|i| {
+            if i > 1 {
+                f.write_str("  ")?;
+            }
+            write!(f, " {i}")
+        })
+    }
+}
+
 impl FromStr for Crates {
-    type Err = !;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut it = s.lines().rev();
+    fn from_str(s: &str) -> Result<Self> {
+        let lines: Vec<_> = s.lines().collect();
 
-        let mut stacks = vec![Vec::new(); it.next().unwrap().len() / 4 + 1];
+        let Some((numbers, rows)) = lines.split_last() else {
+            bail!("missing the row of stack numbers");
+        };
+        // The stacks are numbered 1 to n, however the row is padded.
+        let labels: Vec<_> = numbers.split_whitespace().collect();
+        let numbered = labels
+            .iter()
+            .enumerate()
+            .all(|(i, label)| label.parse() == Ok(i + 1));
+        if labels.is_empty() || !numbered {
+            bail!(
+                "line {}: expected stack numbers 1 to n, found {numbers:?}",
+                lines.len()
+            );
+        }
+        let mut stacks = vec![Vec::new(); labels.len()];
 
-        it.for_each(|line| {
-            line.chars()
+        for (i, line) in rows.iter().enumerate().rev() {
+            let crates = line
+                .chars()
                 .skip(1)
                 .step_by(4)
                 .enumerate()
-                .filter(|(_, c)| c.is_ascii_alphabetic())
-                .for_each(|(i, c)| stacks[i].push(c));
-        });
+                .filter(|(_, c)| c.is_ascii_alphabetic());
+
+            for (column, c) in crates {
+                let Some(stack) = stacks.get_mut(column) else {
+                    bail!(
+                        "line {}: crate {c:?} in column {} has no stack number below it",
+                        i + 1,
+                        column + 1
+                    );
+                };
+                stack.push(c);
+            }
+        }
 
         Ok(Self { stacks })
     }
@@ -59,63 +195,128 @@ struct CraneMove {
     to: usize,
 }
 
+impl fmt::Display for CraneMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
 impl FromStr for CraneMove {
-    type Err = !;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut it = s
-            .split_whitespace()
-            .skip(1)
-            .step_by(2)
-            .map(|n| n.parse().unwrap());
+    fn from_str(s: &str) -> Result<Self> {
+        let words: Vec<_> = s.split_whitespace().collect();
+        let ["move", count, "from", from, "to", to] = words[..] else {
+            bail!("expected \"move N from A to B\", found {s:?}");
+        };
+        let number = |n: &str| -> Result<usize> {
+            n.parse()
+                .with_context(|| format!("invalid number {n:?} in {s:?}"))
+        };
 
         Ok(Self {
-            count: it.next().unwrap(),
-            from: it.next().unwrap(),
-            to: it.next().unwrap(),
+            count: number(count)?,
+            from: number(from)?,
+            to: number(to)?,
         })
     }
 }
 
-fn parse_input(input: &str) -> (Crates, Vec<CraneMove>) {
-    let (fst, snd) = input.split_at(
-        input
-            .find("\n\n")
-            .or_else(|| input.find("\r\n\r\n"))
-            .unwrap(),
-    );
+/// Splits the input at the first blank line into the drawing of the stacks
+/// and the moves, one per non-blank line after it.
+fn parse_input(input: &str) -> Result<(Crates, Vec<CraneMove>)> {
+    let split = input
+        .find("\n\n")
+        .or_else(|| input.find("\r\n\r\n"))
+        .ok_or(anyhow!("no blank line between the stacks and the moves"))?;
+    let drawing = &input[..split];
 
-    (
-        Crates::from_str(fst).unwrap(),
-        snd.trim_start()
-            .lines()
-            .map(CraneMove::from_str)
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap(),
-    )
+    let crates = Crates::from_str(drawing).context("invalid drawing of the stacks")?;
+    let moves = input
+        .lines()
+        .enumerate()
+        .skip(drawing.lines().count())
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| CraneMove::from_str(line).with_context(|| format!("line {}", i + 1)))
+        .collect::<Result<_>>()?;
+
+    Ok((crates, moves))
 }
 
-fn part1(mut crates: Crates, moves: &[CraneMove]) -> String {
-    for m in moves {
-        crates.move_crates_by_one(m);
-    }
+/// Applies every move in turn, stopping at the first one that cannot be
+/// made.
+fn apply(
+    crates: &mut Crates,
+    moves: &[CraneMove],
+    crane: impl Fn(&mut Crates, &CraneMove) -> Result<(), MoveError>,
+) -> Result<()> {
+    moves
+        .iter()
+        .enumerate()
+        .try_for_each(|(i, m)| crane(crates, m).with_context(|| format!("move {} ({m})", i + 1)))
+}
+
+fn part1(mut crates: Crates, moves: &[CraneMove]) -> Result<String> {
+    apply(&mut crates, moves, Crates::move_crates_by_one)?;
 
-    crates.top_crates()
+    Ok(crates.top_crates())
 }
 
-fn part2(mut crates: Crates, moves: &[CraneMove]) -> String {
-    for m in moves {
-        crates.move_crates(m);
-    }
+fn part2(mut crates: Crates, moves: &[CraneMove]) -> Result<String> {
+    apply(&mut crates, moves, Crates::move_crates)?;
 
-    crates.top_crates()
+    Ok(crates.top_crates())
 }
 
-fn main() {
-    let (crates, moves) = parse_input(include_str!("../input.txt"));
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
 
-    println!("Part 1: {}", part1(crates.clone(), &moves));
-    println!("Part 1: {}", part2(crates, &moves));
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Step through the crane moves interactively
+    Repl,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .context("failed to open trace file")?;
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return Ok(());
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .context("failed to read input")?;
+    let (crates, moves) = parse_input(&input)?;
+
+    if let Some(Mode::Repl) = args.mode {
+        repl::run(crates, moves).context("failed to run repl")?;
+        return Ok(());
+    }
+
+    println!(
+        "Part 1: {}",
+        info_span!("part1").in_scope(|| part1(crates.clone(), &moves))?
+    );
+    println!(
+        "Part 2: {}",
+        info_span!("part2").in_scope(|| part2(crates, &moves))?
+    );
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -123,7 +324,7 @@ mod tests {
     use super::*;
 
     fn get_input() -> (Crates, Vec<CraneMove>) {
-        parse_input(include_str!("../test.txt"))
+        parse_input(include_str!("../test.txt")).unwrap()
     }
 
     #[test]
@@ -131,7 +332,7 @@ mod tests {
         let (crates, moves) = get_input();
 
         let expected = "CMZ";
-        let result = part1(crates, &moves);
+        let result = part1(crates, &moves).unwrap();
 
         assert_eq!(expected, result);
     }
@@ -141,8 +342,118 @@ mod tests {
         let (crates, moves) = get_input();
 
         let expected = "MCD";
-        let result = part2(crates, &moves);
+        let result = part2(crates, &moves).unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn rearranged() {
+        let (mut crates, moves) = get_input();
+
+        apply(&mut crates, &moves, Crates::move_crates).unwrap();
+
+        aoc::assert_snapshot!("rearranged", crates);
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let (crates, _) = get_input();
+        let cases = [
+            ("move 1 from 0 to 1", MoveError::StackZero),
+            (
+                "move 1 from 1 to 4",
+                MoveError::UnknownStack {
+                    stack: 4,
+                    stacks: 3,
+                },
+            ),
+            ("move 1 from 2 to 2", MoveError::SameStack(2)),
+            (
+                "move 4 from 2 to 1",
+                MoveError::NotEnoughCrates {
+                    stack: 2,
+                    holds: 3,
+                    count: 4,
+                },
+            ),
+        ];
+
+        for (line, expected) in cases {
+            let m = CraneMove::from_str(line).unwrap();
+            let mut moved = crates.clone();
+
+            assert_eq!(Err(expected), moved.move_crates_by_one(&m));
+            assert_eq!(Err(expected), moved.move_crates(&m));
+            assert_eq!(crates.stacks, moved.stacks);
+        }
+    }
+
+    #[test]
+    fn stops_at_first_bad_move() {
+        let input = include_str!("../test.txt").replace("move 3 from 1", "move 4 from 1");
+        let (crates, moves) = parse_input(&input).unwrap();
+
+        let expected = "move 2 (move 4 from 1 to 3): stack 1 holds 3 crates, cannot take 4";
+        let result = format!("{:#}", part1(crates, &moves).unwrap_err());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn malformed_moves() {
+        let cases = [
+            (
+                "move 1 from 2\n",
+                "line 6: expected \"move N from A to B\", found \"move 1 from 2\"",
+            ),
+            (
+                "move -1 from 2 to 1\n",
+                "line 6: invalid number \"-1\" in \"move -1 from 2 to 1\": \
+                 invalid digit found in string",
+            ),
+        ];
+
+        for (line, expected) in cases {
+            let input = format!("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n{line}");
+            let result = format!("{:#}", parse_input(&input).unwrap_err());
+
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn malformed_drawing() {
+        let input = "[A]\n[A] [B]\n 1\n\nmove 1 from 1 to 1\n";
+
+        let expected = "invalid drawing of the stacks: \
+                        line 2: crate 'B' in column 2 has no stack number below it";
+        let result = format!("{:#}", parse_input(input).unwrap_err());
+
+        assert_eq!(expected, result);
+
+        let input = "[A] [B]\n 1   3\n\nmove 1 from 1 to 2\n";
+
+        let expected = "invalid drawing of the stacks: \
+                        line 2: expected stack numbers 1 to n, found \" 1   3\"";
+        let result = format!("{:#}", parse_input(input).unwrap_err());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn padded_stack_numbers() {
+        let input = include_str!("../test.txt").replace(" 1   2   3 \n", " 1   2   3     \n");
+        let (crates, _) = parse_input(&input).unwrap();
+        let m = CraneMove::from_str("move 1 from 1 to 4").unwrap();
+
+        let expected = MoveError::UnknownStack {
+            stack: 4,
+            stacks: 3,
+        };
+        let result = crates.clone().move_crates(&m).unwrap_err();
 
+        assert_eq!(3, crates.stacks.len());
         assert_eq!(expected, result);
     }
 }
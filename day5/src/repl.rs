@@ -0,0 +1,106 @@
+use std::io;
+
+use aoc::repl::{self, arg, arg_or};
+
+use super::{CraneMove, Crates};
+
+const USAGE: &str = "\
+stacks          print the current stacks
+step [N]        apply the next N moves (default 1)
+top             print the crates on top of each stack
+crane 9000|9001 switch between moving crates one by one or all at once
+reset           go back to the initial stacks
+quit            leave the repl";
+
+struct Session {
+    initial: Crates,
+    crates: Crates,
+    moves: Vec<CraneMove>,
+    next: usize,
+    crane: u16,
+}
+
+impl Session {
+    fn new(crates: Crates, moves: Vec<CraneMove>) -> Self {
+        Self {
+            initial: crates.clone(),
+            crates,
+            moves,
+            next: 0,
+            crane: 9000,
+        }
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "stacks" => Ok(self.crates.to_string()),
+            "step" => {
+                let n = arg_or(args, 0, "N", 1)?;
+                self.step(n)
+            }
+            "top" => Ok(self.crates.top_crates()),
+            "crane" => match arg(args, 0, "model")? {
+                model @ (9000 | 9001) => {
+                    self.crane = model;
+                    Ok(String::new())
+                }
+                model => Err(format!("unknown crane model {model}")),
+            },
+            "reset" => {
+                self.crates = self.initial.clone();
+                self.next = 0;
+                Ok(self.crates.to_string())
+            }
+            _ => Err(format!("unknown command {command:?}, try `help`")),
+        }
+    }
+
+    fn step(&mut self, n: usize) -> Result<String, String> {
+        let remaining = self.moves.len() - self.next;
+        if remaining == 0 {
+            return Err("no moves left".to_string());
+        }
+
+        let mut out = String::new();
+        for m in &self.moves[self.next..self.next + n.min(remaining)] {
+            let moved = match self.crane {
+                9000 => self.crates.move_crates_by_one(m),
+                _ => self.crates.move_crates(m),
+            };
+            if let Err(e) = moved {
+                return Err(format!("{out}move {} ({m}): {e}", self.next + 1));
+            }
+            self.next += 1;
+            out += &format!("{m} ({}/{})\n", self.next, self.moves.len());
+        }
+        out += &self.crates.to_string();
+
+        Ok(out)
+    }
+}
+
+pub fn run(crates: Crates, moves: Vec<CraneMove>) -> io::Result<()> {
+    repl::run(&mut Session::new(crates, moves), USAGE, Session::eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn step() {
+        let (crates, moves) = parse_input(include_str!("../test.txt")).unwrap();
+        let mut session = Session::new(crates, moves);
+
+        let expected = "\
+move 1 from 2 to 1 (1/4)
+[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3";
+        let result = session.eval("step", &[]).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
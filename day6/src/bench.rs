@@ -0,0 +1,21 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{part1, part2};
+
+/// A stream of `n` characters drawn from only three letters, so that both
+/// markers are found right at the end.
+fn datastream(n: usize) -> String {
+    let mut rng = Rng::new(n as u64);
+
+    (0..n)
+        .map(|_| *rng.pick(&['a', 'b', 'c']))
+        .chain("mnopqrstuvwxyz".chars())
+        .collect()
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 10000, datastream, |d| part1(d)),
+        scaling.measure("part2", Complexity::Linear, 10000, datastream, |d| part2(d)),
+    ]);
+}
@@ -1,11 +1,16 @@
 #![feature(array_windows)]
 
+mod bench;
+
 use std::collections::HashSet;
 
+use clap::{Parser, Subcommand};
+use tracing::{debug, info_span};
+
 fn find_unique_len<const N: usize>(input: &str) -> usize {
     let mut hs: HashSet<u8> = HashSet::with_capacity(N);
 
-    input
+    let marker = input
         .as_bytes()
         .array_windows::<N>()
         .map(|window| {
@@ -16,7 +21,10 @@ fn find_unique_len<const N: usize>(input: &str) -> usize {
         .enumerate()
         .find_map(|(i, unique_count)| if unique_count == N { Some(i) } else { None })
         .unwrap()
-        + N
+        + N;
+    debug!(window = N, marker, "found marker");
+
+    marker
 }
 
 fn part1(input: &str) -> usize {
@@ -27,11 +35,39 @@ fn part2(input: &str) -> usize {
     find_unique_len::<14>(input)
 }
 
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
 fn main() {
-    let input = include_str!("../input.txt");
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .expect("failed to read input");
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+    println!("Part 1: {}", info_span!("part1").in_scope(|| part1(&input)));
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(&input)));
 }
 
 #[cfg(test)]
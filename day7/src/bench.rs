@@ -0,0 +1,36 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{parse_input, part1, part2};
+
+/// Terminal output of walking `n` nested directories, each holding a few
+/// files and an extra leaf directory. File sizes are picked so that the disk
+/// is always about 50000000 full, which `part2` relies on.
+fn terminal(n: usize) -> String {
+    let mut rng = Rng::new(n as u64);
+    let max_file = 25_000_000 / n;
+    let mut out = String::from("$ cd /\n");
+
+    for _ in 0..n {
+        out += "$ ls\ndir d\ndir leaf\n";
+        for name in ["a.txt", "b.dat", "c"] {
+            out += &format!("{} {name}\n", rng.range(1..=max_file));
+        }
+        out += &format!(
+            "$ cd leaf\n$ ls\n{} x\n$ cd ..\n$ cd d\n",
+            rng.range(1..=max_file)
+        );
+    }
+
+    out
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 100, terminal, |t| {
+            part1(&parse_input(t))
+        }),
+        scaling.measure("part2", Complexity::Linear, 100, terminal, |t| {
+            part2(&parse_input(t))
+        }),
+    ]);
+}
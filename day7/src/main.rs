@@ -1,4 +1,8 @@
+mod bench;
+mod repl;
+
 use camino::Utf8Path;
+use clap::{Parser, Subcommand};
 use id_tree::{Node, Tree};
 use nom::{
     branch::alt,
@@ -8,6 +12,7 @@ use nom::{
     sequence::{preceded, separated_pair},
     IResult,
 };
+use tracing::{debug, info_span};
 
 #[derive(Debug)]
 enum Command<'a> {
@@ -60,7 +65,7 @@ fn parse_line(i: &str) -> IResult<&str, Line> {
 
 #[derive(Debug)]
 struct FsEntry<'a> {
-    _name: &'a Utf8Path,
+    name: &'a Utf8Path,
     size: u64,
 }
 
@@ -71,7 +76,7 @@ fn parse_input(i: &str) -> Tree<FsEntry> {
     let root = tree
         .insert(
             Node::new(FsEntry {
-                _name: "/".into(),
+                name: "/".into(),
                 size: 0,
             }),
             AsRoot,
@@ -92,7 +97,7 @@ fn parse_input(i: &str) -> Tree<FsEntry> {
                         let dir = tree
                             .insert(
                                 Node::new(FsEntry {
-                                    _name: path,
+                                    name: path,
                                     size: 0,
                                 }),
                                 UnderNode(&current),
@@ -105,11 +110,8 @@ fn parse_input(i: &str) -> Tree<FsEntry> {
             Line::Entry(e) => match e {
                 Entry::Directory(_) => {}
                 Entry::File(size, name) => {
-                    tree.insert(
-                        Node::new(FsEntry { _name: name, size }),
-                        UnderNode(&current),
-                    )
-                    .unwrap();
+                    tree.insert(Node::new(FsEntry { name, size }), UnderNode(&current))
+                        .unwrap();
                 }
             },
         });
@@ -125,13 +127,19 @@ fn entries_size(tree: &Tree<FsEntry>, node: &Node<FsEntry>) -> u64 {
             .fold(0, |acc, e| acc + entries_size(tree, tree.get(e).unwrap()))
 }
 
-fn part1(tree: &Tree<FsEntry>) -> u64 {
+fn dir_sizes<'a>(tree: &'a Tree<FsEntry<'a>>) -> impl Iterator<Item = u64> + 'a {
     tree.traverse_pre_order(tree.root_node_id().unwrap())
         .unwrap()
         .filter(|n| !n.children().is_empty())
-        .map(|node| entries_size(tree, node))
-        .filter(|&s| s < 100000)
-        .sum()
+        .map(|node| {
+            let size = entries_size(tree, node);
+            debug!(dir = %node.data().name, size, "directory size");
+            size
+        })
+}
+
+fn part1(tree: &Tree<FsEntry>) -> u64 {
+    dir_sizes(tree).filter(|&s| s < 100000).sum()
 }
 
 fn part2(tree: &Tree<FsEntry>) -> u64 {
@@ -139,20 +147,50 @@ fn part2(tree: &Tree<FsEntry>) -> u64 {
     let free_space = 70000000 - entries_size(tree, tree.get(root_node).unwrap());
     let to_free = 30000000 - free_space;
 
-    tree.traverse_pre_order(root_node)
-        .unwrap()
-        .filter(|n| !n.children().is_empty())
-        .map(|node| entries_size(tree, node))
-        .filter(|&s| s > to_free)
-        .min()
-        .unwrap()
+    dir_sizes(tree).filter(|&s| s > to_free).min().unwrap()
+}
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Explore the reconstructed filesystem interactively
+    Repl,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
 }
 
 fn main() {
-    let tree = parse_input(include_str!("../input.txt"));
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .expect("failed to read input");
+    let tree = parse_input(&input);
+
+    if let Some(Mode::Repl) = args.mode {
+        repl::run(tree).expect("failed to run repl");
+        return;
+    }
 
-    println!("Part 1: {}", part1(&tree));
-    println!("Part 2: {}", part2(&tree));
+    println!("Part 1: {}", info_span!("part1").in_scope(|| part1(&tree)));
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(&tree)));
 }
 
 #[cfg(test)]
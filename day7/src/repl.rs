@@ -0,0 +1,81 @@
+use std::io;
+
+use camino::{Utf8Component, Utf8Path};
+use id_tree::{NodeId, Tree};
+
+use aoc::repl;
+
+use super::{entries_size, FsEntry};
+
+const USAGE: &str = "\
+du [PATH]  print the total size of PATH (default /)
+ls [PATH]  list the entries of directory PATH (default /)
+quit       leave the repl";
+
+fn resolve<'t>(tree: &'t Tree<FsEntry>, path: &Utf8Path) -> Result<&'t NodeId, String> {
+    let root = tree.root_node_id().unwrap();
+
+    path.components()
+        .try_fold(root, |current, component| match component {
+            Utf8Component::RootDir | Utf8Component::Prefix(_) => Ok(root),
+            Utf8Component::CurDir => Ok(current),
+            Utf8Component::ParentDir => Ok(tree.get(current).unwrap().parent().unwrap_or(root)),
+            Utf8Component::Normal(name) => tree
+                .get(current)
+                .unwrap()
+                .children()
+                .iter()
+                .find(|&id| tree.get(id).unwrap().data().name.as_str() == name)
+                .ok_or_else(|| format!("{path}: no such file or directory")),
+        })
+}
+
+fn eval(tree: &mut Tree<FsEntry>, command: &str, args: &[&str]) -> Result<String, String> {
+    let path = Utf8Path::new(args.first().copied().unwrap_or("/"));
+    let node = tree.get(resolve(tree, path)?).unwrap();
+
+    match command {
+        "du" => Ok(format!("{}\t{path}", entries_size(tree, node))),
+        "ls" => {
+            if node.children().is_empty() {
+                return Err(format!("{path}: not a directory"));
+            }
+
+            Ok(node
+                .children()
+                .iter()
+                .map(|id| {
+                    let child = tree.get(id).unwrap();
+                    let FsEntry { name, size } = child.data();
+                    if child.children().is_empty() {
+                        format!("{size} {name}")
+                    } else {
+                        format!("dir {name}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        _ => Err(format!("unknown command {command:?}, try `help`")),
+    }
+}
+
+pub fn run(mut tree: Tree<FsEntry>) -> io::Result<()> {
+    repl::run(&mut tree, USAGE, eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn du() {
+        let mut tree = parse_input(include_str!("../test.txt"));
+
+        let expected = "584\t/a/e";
+        let result = eval(&mut tree, "du", &["/a/e"]).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
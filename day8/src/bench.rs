@@ -0,0 +1,49 @@
+use ndarray::Array2;
+
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{parse_input, part1, part2};
+
+/// A square grid of roughly `n` random trees.
+fn grid(n: usize) -> Array2<u8> {
+    let mut rng = Rng::new(n as u64);
+    let side = (n as f64).sqrt().round() as usize;
+
+    let input: String = (0..side)
+        .map(|_| {
+            let mut row: String = (0..side)
+                .map(|_| char::from_digit(rng.range(0..=9) as u32, 10).unwrap())
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect();
+
+    parse_input(&input)
+}
+
+/// A square grid of roughly `n` trees growing one height every tenth of the
+/// rows. Scanning down from the top edge, `part1` passes every shorter row
+/// before it meets one as tall, so its scans no longer stop early.
+fn ramp(n: usize) -> Array2<u8> {
+    let side = (n as f64).sqrt().round() as usize;
+
+    let input: String = (0..side)
+        .map(|x| {
+            let height = char::from_digit((10 * x / side) as u32, 10).unwrap();
+            let mut row: String = (0..side).map(|_| height).collect();
+            row.push('\n');
+            row
+        })
+        .collect();
+
+    parse_input(&input)
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 40000, grid, part1),
+        scaling.measure("part2", Complexity::Linear, 40000, grid, part2),
+        scaling.measure("part1 ramp", Complexity::Linear, 40000, ramp, part1),
+    ]);
+}
@@ -1,6 +1,9 @@
-#![feature(iter_collect_into)]
+mod bench;
+mod repl;
 
+use clap::{Parser, Subcommand};
 use ndarray::{s, Array2};
+use tracing::{info_span, trace};
 
 fn parse_input(i: &str) -> Array2<u8> {
     let width = i.lines().next().unwrap().len();
@@ -28,43 +31,74 @@ fn part1(i: &Array2<u8>) -> usize {
     visible.iter().filter(|&&v| v).count()
 }
 
-fn part2(i: &Array2<u8>) -> usize {
-    let mut buf: Vec<u8> = Vec::new();
+fn scenic_score(i: &Array2<u8>, (x, y): (usize, usize)) -> usize {
+    let val = i[(x, y)];
+
+    // Each slice runs outwards from the tree, hence the reversed ones.
+    [s![..x;-1, y], s![x + 1.., y], s![x, ..y;-1], s![x, y + 1..]]
+        .into_iter()
+        .map(|s| {
+            let view = i.slice(s);
+            view.iter()
+                .position(|&v| v >= val)
+                .map_or(view.len(), |p| p + 1)
+        })
+        .product()
+}
 
+fn part2(i: &Array2<u8>) -> usize {
     i.indexed_iter()
-        .map(|((x, y), &val)| {
-            [s![..x, y], s![x + 1.., y], s![x, ..y], s![x, y + 1..]]
-                .into_iter()
-                .enumerate()
-                .map(|(idx, s)| {
-                    buf.clear();
-                    i.slice(s).iter().collect_into(&mut buf);
-
-                    if idx % 2 == 0 {
-                        buf.reverse();
-                    }
-
-                    let mut counter = 0;
-                    for &v in &buf {
-                        counter += 1;
-                        if v >= val {
-                            break;
-                        }
-                    }
-                    counter
-                })
-                .product()
+        .map(|((x, y), _)| {
+            let score = scenic_score(i, (x, y));
+            trace!(x, y, score, "scenic score");
+
+            score
         })
-        // .inspect(|v| println!("{v}"))
         .max()
         .unwrap()
 }
 
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Inspect the tree grid interactively
+    Repl,
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
 fn main() {
-    let map = parse_input(include_str!("../input.txt"));
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = args
+        .common
+        .read_input(include_str!("../input.txt"))
+        .expect("failed to read input");
+    let map = parse_input(&input);
+
+    if let Some(Mode::Repl) = args.mode {
+        repl::run(map).expect("failed to run repl");
+        return;
+    }
 
-    println!("Part 1: {}", part1(&map));
-    println!("Part 2: {}", part2(&map));
+    println!("Part 1: {}", info_span!("part1").in_scope(|| part1(&map)));
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(&map)));
 }
 
 #[cfg(test)]
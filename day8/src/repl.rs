@@ -0,0 +1,50 @@
+use std::io;
+
+use ndarray::Array2;
+
+use aoc::repl::{self, arg};
+
+use super::scenic_score;
+
+const USAGE: &str = "\
+height ROW COL  print the height of the tree at ROW, COL (0-based)
+score ROW COL   print the scenic score of the tree at ROW, COL
+quit            leave the repl";
+
+fn tree_at(map: &Array2<u8>, args: &[&str]) -> Result<(usize, usize), String> {
+    let pos = (arg(args, 0, "ROW")?, arg(args, 1, "COL")?);
+    if map.get(pos).is_none() {
+        let [rows, cols]: [_; 2] = map.shape().try_into().unwrap();
+        return Err(format!("{pos:?} is outside of the {rows}x{cols} grid"));
+    }
+
+    Ok(pos)
+}
+
+fn eval(map: &mut Array2<u8>, command: &str, args: &[&str]) -> Result<String, String> {
+    match command {
+        "height" => Ok(map[tree_at(map, args)?].to_string()),
+        "score" => Ok(scenic_score(map, tree_at(map, args)?).to_string()),
+        _ => Err(format!("unknown command {command:?}, try `help`")),
+    }
+}
+
+pub fn run(mut map: Array2<u8>) -> io::Result<()> {
+    repl::run(&mut map, USAGE, eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn height() {
+        let mut map = parse_input(include_str!("../test.txt"));
+
+        let expected = "5";
+        let result = eval(&mut map, "height", &["3", "2"]).unwrap();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -0,0 +1,27 @@
+use aoc::bench::{self, Complexity, Rng, Scaling};
+
+use super::{part1, part2, Direction, Move};
+
+fn motions(n: usize) -> Vec<Move> {
+    let mut rng = Rng::new(n as u64);
+    let directions = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Right,
+        Direction::Left,
+    ];
+
+    (0..n)
+        .map(|_| Move {
+            direction: *rng.pick(&directions),
+            count: rng.range(1..=20),
+        })
+        .collect()
+}
+
+pub fn run(scaling: &Scaling) {
+    bench::check(&[
+        scaling.measure("part1", Complexity::Linear, 1000, motions, |m| part1(m)),
+        scaling.measure("part2", Complexity::Linear, 1000, motions, |m| part2(m)),
+    ]);
+}
@@ -1,9 +1,13 @@
+mod bench;
+
 use std::{
     collections::HashSet,
     ops::{Add, AddAssign, Sub},
 };
 
+use clap::{Parser, Subcommand};
 use itertools::Itertools;
+use tracing::{info_span, trace};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 struct Point(i32, i32);
@@ -121,6 +125,7 @@ fn part1(i: &[Move]) -> usize {
     i.iter().for_each(|m| {
         (0..m.count).for_each(|_| {
             line.move_line(m.direction);
+            trace!(direction = ?m.direction, knots = ?line.body, "step");
             hs.insert(line.body[1]);
         });
     });
@@ -135,6 +140,7 @@ fn part2(i: &[Move]) -> usize {
     i.iter().for_each(|m| {
         (0..m.count).for_each(|_| {
             line.move_line(m.direction);
+            trace!(direction = ?m.direction, knots = ?line.body, "step");
             hs.insert(line.body[9]);
         });
     });
@@ -142,11 +148,41 @@ fn part2(i: &[Move]) -> usize {
     hs.len()
 }
 
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    common: aoc::Common,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Check how the solvers scale on generated inputs of growing size
+    Bench(aoc::bench::Scaling),
+}
+
 fn main() {
-    let input = parse_input(include_str!("../input.txt"));
+    let args = Args::parse();
+    args.common
+        .init_tracing()
+        .expect("failed to open trace file");
+
+    if let Some(Mode::Bench(scaling)) = &args.mode {
+        bench::run(scaling);
+        return;
+    }
+
+    let input = parse_input(
+        &args
+            .common
+            .read_input(include_str!("../input.txt"))
+            .expect("failed to read input"),
+    );
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    println!("Part 1: {}", info_span!("part1").in_scope(|| part1(&input)));
+    println!("Part 2: {}", info_span!("part2").in_scope(|| part2(&input)));
 }
 
 #[cfg(test)]